@@ -0,0 +1,13 @@
+use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+use libtock_platform::{ErrorCode, Syscalls};
+use libtock_runtime::TockSyscalls;
+
+#[alloc_error_handler]
+fn alloc_error_handler(layout: core::alloc::Layout) -> ! {
+    // Signal the failure using the LowLevelDebug capsule (if available),
+    // followed by the size of the failed allocation.
+    LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    LowLevelDebug::<TockSyscalls>::print_1(layout.size() as u32);
+
+    TockSyscalls::exit_terminate(ErrorCode::NoMem as u32);
+}
@@ -0,0 +1,222 @@
+//! `libtock_alloc` provides a heap allocator for Tock processes. The heap
+//! starts at the initial process break (immediately after `.bss`), and the
+//! allocator grows it on demand using the Memop sbrk operation.
+//!
+//! To use `alloc` types such as `alloc::vec::Vec`, a process binary declares a
+//! global allocator:
+//! ```ignore
+//! extern crate alloc;
+//!
+//! #[global_allocator]
+//! static ALLOCATOR: libtock_alloc::TockAllocator = libtock_alloc::TockAllocator::new();
+//! ```
+//! `libtock_alloc` also provides an allocation error handler, which reports the
+//! failure through `LowLevelDebug` and terminates the process.
+
+#![cfg_attr(
+    any(target_arch = "arm", target_arch = "riscv32"),
+    feature(alloc_error_handler)
+)]
+#![no_std]
+#![warn(unsafe_op_in_unsafe_fn)]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform::Syscalls;
+
+#[cfg(any(target_arch = "arm", target_arch = "riscv32"))]
+mod error_handler;
+
+/// The global allocator type for Tock process binaries.
+#[cfg(any(target_arch = "arm", target_arch = "riscv32"))]
+pub type TockAllocator = Allocator<libtock_runtime::TockSyscalls>;
+
+/// A heap allocator that grows the process break as needed.
+///
+/// `Allocator` is a bump allocator: memory is allocated from the top of the
+/// heap, and the process break is moved up when the heap runs out of room.
+/// Freeing (or resizing) the most recent allocation returns its memory to the
+/// heap, which makes growing a single `Vec` or `String` efficient. Memory freed
+/// from other allocations is not reused. `Allocator` never moves the process
+/// break down.
+// S is not bounded by Syscalls so that the constructors can be const fns.
+pub struct Allocator<S> {
+    // The start of the heap, or null if the heap has not been initialized. The
+    // heap is initialized by the first allocation.
+    heap_start: Cell<*mut u8>,
+
+    // The offset of the top of the heap (the end of the most recent
+    // allocation) from heap_start.
+    top: Cell<usize>,
+
+    // The offset of the process break from heap_start.
+    brk: Cell<usize>,
+
+    max_size: usize,
+    used: Cell<usize>,
+    peak: Cell<usize>,
+
+    _syscalls: PhantomData<S>,
+}
+
+/// Statistics about an [`Allocator`]'s heap.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HeapStats {
+    /// The number of bytes currently allocated.
+    pub used: usize,
+
+    /// The largest value `used` has had.
+    pub peak: usize,
+
+    /// The process break, which is the end of the heap. Null if nothing has
+    /// been allocated yet.
+    pub brk: *const u8,
+}
+
+impl<S> Allocator<S> {
+    /// Creates an allocator whose heap may grow until the process runs out of
+    /// memory.
+    pub const fn new() -> Self {
+        Self::with_max_size(usize::MAX)
+    }
+
+    /// Creates an allocator whose heap will not grow larger than `max_size`
+    /// bytes. Alignment padding counts towards the maximum.
+    pub const fn with_max_size(max_size: usize) -> Self {
+        Self {
+            heap_start: Cell::new(core::ptr::null_mut()),
+            top: Cell::new(0),
+            brk: Cell::new(0),
+            max_size,
+            used: Cell::new(0),
+            peak: Cell::new(0),
+            _syscalls: PhantomData,
+        }
+    }
+}
+
+impl<S: Syscalls> Allocator<S> {
+    /// Returns the heap's current statistics.
+    pub fn stats(&self) -> HeapStats {
+        let heap_start = self.heap_start.get();
+        HeapStats {
+            used: self.used.get(),
+            peak: self.peak.get(),
+            brk: heap_start.wrapping_add(self.brk.get()),
+        }
+    }
+
+    // Returns the start of the heap, initializing the heap if necessary.
+    fn heap_start(&self) -> Option<*mut u8> {
+        if self.heap_start.get().is_null() {
+            // Safety: An increment of 0 does not move the process break.
+            let heap_start = unsafe { S::memop_sbrk(0) }.ok()?;
+            self.heap_start.set(heap_start);
+        }
+        Some(self.heap_start.get())
+    }
+
+    // Moves the top of the heap to `new_top`, growing the process break if
+    // necessary. Returns None if the heap cannot grow that far.
+    fn set_top(&self, new_top: usize) -> Option<()> {
+        if new_top > self.max_size {
+            return None;
+        }
+        if new_top > self.brk.get() {
+            let increment = (new_top - self.brk.get()).try_into().ok()?;
+            // Safety: The memory above the process break is not in use.
+            unsafe { S::memop_sbrk(increment) }.ok()?;
+            self.brk.set(new_top);
+        }
+        self.top.set(new_top);
+        Some(())
+    }
+
+    // Adds `size` to the number of bytes in use.
+    fn add_used(&self, size: usize) {
+        let used = self.used.get() + size;
+        self.used.set(used);
+        self.peak.set(self.peak.get().max(used));
+    }
+
+    // Returns true if `ptr` (with size `size`) is the most recent allocation.
+    fn is_top(&self, ptr: *mut u8, size: usize) -> bool {
+        ptr as usize + size == self.heap_start.get() as usize + self.top.get()
+    }
+}
+
+impl<S> Default for Allocator<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<S: Syscalls> GlobalAlloc for Allocator<S> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap_start = match self.heap_start() {
+            Some(heap_start) => heap_start,
+            None => return core::ptr::null_mut(),
+        };
+        let top_address = heap_start as usize + self.top.get();
+        // Layout guarantees the alignment is a power of two.
+        let padding = top_address.wrapping_neg() & (layout.align() - 1);
+        let start = self.top.get() + padding;
+        let new_top = match start.checked_add(layout.size()) {
+            Some(new_top) => new_top,
+            None => return core::ptr::null_mut(),
+        };
+        if self.set_top(new_top).is_none() {
+            return core::ptr::null_mut();
+        }
+        self.add_used(layout.size());
+        heap_start.wrapping_add(start)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.used.set(self.used.get() - layout.size());
+        if self.is_top(ptr, layout.size()) {
+            self.top.set(self.top.get() - layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // Resize the most recent allocation in place.
+        if self.is_top(ptr, layout.size()) {
+            let start = self.top.get() - layout.size();
+            if let Some(new_top) = start.checked_add(new_size) {
+                if self.set_top(new_top).is_some() {
+                    self.used.set(self.used.get() - layout.size());
+                    self.add_used(new_size);
+                    return ptr;
+                }
+            }
+        }
+
+        // Safety: The caller guarantees new_size is valid for layout's
+        // alignment.
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+        // Safety: The caller guarantees new_size is nonzero.
+        let new_ptr = unsafe { self.alloc(new_layout) };
+        if !new_ptr.is_null() {
+            // Safety: Both allocations are valid for the smaller of the two
+            // sizes, and new_ptr is a new allocation so they do not overlap.
+            unsafe {
+                core::ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+                self.dealloc(ptr, layout);
+            }
+        }
+        new_ptr
+    }
+}
+
+// Safety: Tock processes are single-threaded, and upcalls only run during
+// Yield, which the allocator never calls. Therefore the allocator is never
+// accessed concurrently. This is only true of Tock processes, so other
+// Syscalls implementations (which may be used by multi-threaded host programs)
+// do not get a Sync allocator.
+#[cfg(any(target_arch = "arm", target_arch = "riscv32"))]
+unsafe impl Sync for Allocator<libtock_runtime::TockSyscalls> {}
+
+#[cfg(test)]
+mod tests;
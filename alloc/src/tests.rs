@@ -0,0 +1,157 @@
+use super::{Allocator, HeapStats};
+use core::alloc::{GlobalAlloc, Layout};
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, ExpectedSyscall};
+
+// Points the fake kernel's RAM at `ram`, with the grant region starting
+// `heap_size` bytes in.
+fn set_ram(kernel: &fake::Kernel, ram: &mut [u64], heap_size: usize) -> *mut u8 {
+    let ram_start = ram.as_mut_ptr() as *mut u8;
+    kernel.set_memory_layout(fake::MemoryLayout {
+        ram_start,
+        ram_end: ram_start.wrapping_add(core::mem::size_of_val(ram)),
+        grant_region_start: ram_start.wrapping_add(heap_size),
+        ..Default::default()
+    });
+    ram_start
+}
+
+fn layout(size: usize, align: usize) -> Layout {
+    Layout::from_size_align(size, align).unwrap()
+}
+
+#[test]
+fn alloc() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u64; 8];
+    let ram_start = set_ram(&kernel, &mut ram, 64);
+    let allocator = Allocator::<fake::Syscalls>::new();
+    assert_eq!(
+        allocator.stats(),
+        HeapStats {
+            used: 0,
+            peak: 0,
+            brk: core::ptr::null(),
+        }
+    );
+
+    let a = unsafe { allocator.alloc(layout(3, 1)) };
+    assert_eq!(a, ram_start);
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(3));
+    let b = unsafe { allocator.alloc(layout(8, 4)) };
+    assert_eq!(b, ram_start.wrapping_add(4));
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(12));
+    assert_eq!(
+        allocator.stats(),
+        HeapStats {
+            used: 11,
+            peak: 11,
+            brk: ram_start.wrapping_add(12),
+        }
+    );
+
+    // The allocations must be usable.
+    unsafe {
+        a.write(1);
+        b.cast::<u32>().write(2);
+    }
+    assert_eq!(ram[0].to_ne_bytes()[0], 1);
+
+    // Running out of memory returns null without moving the break.
+    assert!(unsafe { allocator.alloc(layout(53, 1)) }.is_null());
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(12));
+    assert_eq!(allocator.stats().used, 11);
+}
+
+#[test]
+fn dealloc() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u64; 8];
+    let ram_start = set_ram(&kernel, &mut ram, 64);
+    let allocator = Allocator::<fake::Syscalls>::new();
+
+    let a = unsafe { allocator.alloc(layout(4, 1)) };
+    let b = unsafe { allocator.alloc(layout(4, 1)) };
+    // Freeing the most recent allocation makes its memory available again.
+    unsafe { allocator.dealloc(b, layout(4, 1)) };
+    assert_eq!(unsafe { allocator.alloc(layout(4, 1)) }, b);
+    // Other freed memory is not reused.
+    unsafe { allocator.dealloc(a, layout(4, 1)) };
+    assert_eq!(
+        unsafe { allocator.alloc(layout(4, 1)) },
+        ram_start.wrapping_add(8)
+    );
+    assert_eq!(
+        allocator.stats(),
+        HeapStats {
+            used: 8,
+            peak: 8,
+            brk: ram_start.wrapping_add(12),
+        }
+    );
+}
+
+#[test]
+fn realloc() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u64; 8];
+    let ram_start = set_ram(&kernel, &mut ram, 64);
+    let allocator = Allocator::<fake::Syscalls>::new();
+
+    // The most recent allocation is resized in place.
+    let a = unsafe { allocator.alloc(layout(4, 1)) };
+    unsafe { a.write(7) };
+    assert_eq!(unsafe { allocator.realloc(a, layout(4, 1), 16) }, a);
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(16));
+    assert_eq!(unsafe { allocator.realloc(a, layout(16, 1), 8) }, a);
+
+    // Other allocations are moved, and keep their contents.
+    let b = unsafe { allocator.alloc(layout(4, 1)) };
+    assert_eq!(b, ram_start.wrapping_add(8));
+    let a = unsafe { allocator.realloc(a, layout(8, 1), 12) };
+    assert_eq!(a, ram_start.wrapping_add(12));
+    assert_eq!(unsafe { a.read() }, 7);
+    // The peak includes both copies of a, which existed simultaneously.
+    assert_eq!(
+        allocator.stats(),
+        HeapStats {
+            used: 16,
+            peak: 24,
+            brk: ram_start.wrapping_add(24),
+        }
+    );
+}
+
+#[test]
+fn max_size() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u64; 8];
+    let ram_start = set_ram(&kernel, &mut ram, 64);
+    let allocator = Allocator::<fake::Syscalls>::with_max_size(16);
+
+    let a = unsafe { allocator.alloc(layout(12, 1)) };
+    assert_eq!(a, ram_start);
+    assert!(unsafe { allocator.alloc(layout(8, 1)) }.is_null());
+    assert!(unsafe { allocator.realloc(a, layout(12, 1), 17) }.is_null());
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(12));
+    assert_eq!(
+        unsafe { allocator.alloc(layout(4, 1)) },
+        ram_start.wrapping_add(12)
+    );
+}
+
+#[test]
+fn sbrk_failure() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u64; 8];
+    set_ram(&kernel, &mut ram, 64);
+    let allocator = Allocator::<fake::Syscalls>::new();
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: libtock_platform::memop_id::SBRK,
+        argument0: 0,
+        return_error: Some(ErrorCode::NoMem),
+    });
+    assert!(unsafe { allocator.alloc(layout(4, 1)) }.is_null());
+    assert_eq!(allocator.stats().peak, 0);
+}
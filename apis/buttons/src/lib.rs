@@ -1,7 +1,10 @@
 #![no_std]
 
 use libtock_platform::{
-    share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+    future::SubscribeFuture,
+    share::Handle,
+    subscribe::{Decode, OneId},
+    DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
 /// The Buttons driver
@@ -97,13 +100,28 @@ impl<S: Syscalls> Buttons<S> {
     /// There can be only one single listener registered at a time.
     /// Each time this function is used, it will replace the
     /// previously registered listener.
-    pub fn register_listener<'share, F: Fn(u32, ButtonState)>(
-        listener: &'share ButtonListener<F>,
+    ///
+    /// `listener` is generally a `ButtonListener` or a
+    /// `subscribe::DecodedUpcall<ButtonEvent, _>`.
+    pub fn register_listener<'share, U: Upcall<OneId<DRIVER_NUM, 0>>>(
+        listener: &'share U,
         subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
     ) -> Result<(), ErrorCode> {
         S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
     }
 
+    /// Waits for the next button event, and returns the button's index and its
+    /// new state. Events are only generated for buttons whose interrupts have
+    /// been enabled with `enable_interrupts`.
+    ///
+    /// While the returned future is pending it replaces any registered
+    /// listener, and it unregisters the listener when it completes.
+    pub async fn wait_for_event() -> Result<(u32, ButtonState), ErrorCode> {
+        let (arg0, arg1, arg2) =
+            SubscribeFuture::<S, DefaultConfig, DRIVER_NUM, 0, _>::new(|| Ok(())).await?;
+        Ok(ButtonEvent::decode(arg0, arg1, arg2))
+    }
+
     /// Unregister the events listener
     ///
     /// This function may be used even if there was no
@@ -124,8 +142,28 @@ impl<S: Syscalls> Buttons<S> {
 pub struct ButtonListener<F: Fn(u32, ButtonState)>(pub F);
 
 impl<F: Fn(u32, ButtonState)> Upcall<OneId<DRIVER_NUM, 0>> for ButtonListener<F> {
-    fn upcall(&self, button_index: u32, state: u32, _arg2: u32) {
-        self.0(button_index, state.into())
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        let (button_index, state) = ButtonEvent::decode(arg0, arg1, arg2);
+        self.0(button_index, state)
+    }
+}
+
+/// Decodes a button event upcall into the button's index and its new state.
+/// Can be used with `subscribe::DecodedUpcall`:
+///
+/// ```ignore
+/// let listener = DecodedUpcall::<ButtonEvent, _>::new(|(button, state)| {
+///     // make use of the button's state
+/// });
+/// ```
+pub struct ButtonEvent;
+
+impl Decode for ButtonEvent {
+    type SupportedIds = OneId<DRIVER_NUM, 0>;
+    type Args = (u32, ButtonState);
+
+    fn decode(button_index: u32, state: u32, _arg2: u32) -> (u32, ButtonState) {
+        (button_index, state.into())
     }
 }
 #[cfg(test)]
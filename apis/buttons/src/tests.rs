@@ -1,9 +1,11 @@
+extern crate std;
+
 use core::cell::Cell;
 
-use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_platform::{share, subscribe::DecodedUpcall, ErrorCode, Syscalls, YieldNoWaitReturn};
 use libtock_unittest::{fake, upcall};
 
-use crate::{ButtonListener, DRIVER_NUM};
+use crate::{ButtonEvent, ButtonListener, DRIVER_NUM};
 
 use super::ButtonState;
 
@@ -114,3 +116,53 @@ fn subscribe() {
     });
     assert!(!pressed_interrupt_count.get());
 }
+
+#[test]
+fn decoded_listener() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<10>::new();
+    kernel.add_driver(&driver);
+
+    let event = Cell::new(None);
+    let listener = DecodedUpcall::<ButtonEvent, _>::new(|args| event.set(Some(args)));
+    share::scope(|subscribe| {
+        assert_eq!(Buttons::enable_interrupts(3), Ok(()));
+        assert_eq!(Buttons::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(driver.set_pressed(3, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    assert_eq!(event.get(), Some((3, ButtonState::Pressed)));
+}
+
+#[test]
+fn wait_for_event() {
+    use std::boxed::Box;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct NoopWaker;
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut context = Context::from_waker(&waker);
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<10>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Buttons::enable_interrupts(1), Ok(()));
+    let mut future = Box::pin(Buttons::wait_for_event());
+    assert_eq!(future.as_mut().poll(&mut context), Poll::Pending);
+    assert_eq!(driver.set_pressed(1, true), Ok(()));
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    assert_eq!(
+        future.as_mut().poll(&mut context),
+        Poll::Ready(Ok((1, ButtonState::Pressed)))
+    );
+
+    // The future unsubscribed when it completed.
+    assert_eq!(driver.set_pressed(1, false), Ok(()));
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+}
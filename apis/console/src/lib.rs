@@ -0,0 +1,360 @@
+#![no_std]
+
+// Unit tests run on the host, where the console's state is thread-local.
+#[cfg(not(target_os = "none"))]
+extern crate std;
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform::{share, AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe, Syscalls};
+
+/// The console driver. It writes bytes to and reads bytes from the board's
+/// console (usually a UART).
+///
+/// # Example
+/// ```ignore
+/// use libtock2::console::Console;
+///
+/// Console::write(b"Hello, world!\n")?;
+///
+/// let mut buffer = [0; 8];
+/// let len = Console::read(&mut buffer)?;
+///
+/// // Console::writer() can be used with ufmt::uwrite! and core::write!.
+/// ufmt::uwriteln!(Console::writer(), "Read {} bytes", len)?;
+/// ```
+pub struct Console<S: Syscalls>(S);
+
+impl<S: Syscalls> Console<S> {
+    /// Run a check against the console capsule to ensure it is present.
+    ///
+    /// Returns `true` if the driver was present. This does not necessarily mean
+    /// that the driver is working.
+    pub fn exists() -> bool {
+        S::command(DRIVER_NUM, EXISTS, 0, 0).is_success()
+    }
+
+    /// Writes all of `bytes` to the console, and returns once they have been
+    /// written. The kernel may write fewer bytes than requested (e.g. if
+    /// `bytes` is larger than its buffer), in which case the remaining bytes
+    /// are written in further chunks.
+    ///
+    /// If `write` is called from an upcall that runs while another write (or
+    /// print) waits for the kernel, writing would replace that write's buffer
+    /// and upcall. Instead, the bytes are queued (up to `PENDING_LEN` bytes;
+    /// any excess is dropped), and are written once the other write completes.
+    pub fn write(bytes: &[u8]) -> Result<(), ErrorCode> {
+        if state::replace_writing(true) {
+            state::queue(bytes);
+            return Ok(());
+        }
+        let result = Self::write_all(bytes);
+        Self::write_pending();
+        state::replace_writing(false);
+        result
+    }
+
+    /// Reads up to `buffer.len()` bytes from the console into `buffer`, and
+    /// returns the number of bytes read. Blocks until the kernel completes the
+    /// read.
+    pub fn read(buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let len = buffer.len() as u32;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READ }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, READ, len, 0).to_result()?;
+            loop {
+                S::yield_wait();
+                if let Some((status, len)) = done.get() {
+                    return match ErrorCode::try_from(status) {
+                        Ok(error) => Err(error),
+                        Err(_) => Ok(len as usize),
+                    };
+                }
+            }
+        })
+    }
+
+    /// Returns `true` if a write or print is in progress, i.e. if this is called
+    /// from an upcall that runs while a write waits for the kernel. Writes made
+    /// while a write is in progress are queued until it completes, so code that
+    /// never returns to the interrupted write (such as a panic handler) must
+    /// not rely on them being written.
+    pub fn writing() -> bool {
+        state::writing()
+    }
+
+    /// Returns a `ConsoleWriter`, which implements `ufmt::uWrite` and
+    /// `core::fmt::Write` by writing to the console.
+    pub fn writer() -> ConsoleWriter<S> {
+        ConsoleWriter {
+            syscalls: PhantomData,
+        }
+    }
+
+    /// Starts a print, returning a `PrintWriter` that buffers formatted text
+    /// and writes it to the console. Text written by upcalls during the print
+    /// (e.g. by nested prints) is queued until the print completes, so it is
+    /// not interleaved with the print's text. The text is written by the time
+    /// the `PrintWriter` is dropped, unless the print is itself nested within
+    /// another write, in which case it is queued.
+    pub fn print_writer() -> PrintWriter<S> {
+        PrintWriter {
+            nested: state::replace_writing(true),
+            buffer: [0; PRINT_BUFFER_LEN],
+            len: 0,
+            syscalls: PhantomData,
+        }
+    }
+
+    // Writes all of `bytes`, without checking whether another write is in
+    // progress.
+    fn write_all(mut bytes: &[u8]) -> Result<(), ErrorCode> {
+        while !bytes.is_empty() {
+            let written = Self::write_chunk(bytes)?;
+            // If the kernel made no progress, trying again would loop forever.
+            if written == 0 {
+                return Err(ErrorCode::Fail);
+            }
+            bytes = &bytes[written.min(bytes.len())..];
+        }
+        Ok(())
+    }
+
+    // Writes the bytes queued by writes made from upcalls, which may queue more
+    // bytes while we wait for the kernel. The queued writes have already
+    // returned, so errors are ignored.
+    fn write_pending() {
+        let mut buffer = [0; PENDING_LEN];
+        loop {
+            let len = state::take_pending(&mut buffer);
+            if len == 0 {
+                return;
+            }
+            let _ = Self::write_all(&buffer[..len]);
+        }
+    }
+
+    // Writes a single chunk of bytes, and returns the number of bytes the
+    // kernel wrote.
+    fn write_chunk(bytes: &[u8]) -> Result<usize, ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::WRITE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, bytes)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::WRITE }>(
+                subscribe, &done,
+            )?;
+            S::command(DRIVER_NUM, WRITE, bytes.len() as u32, 0).to_result()?;
+            loop {
+                S::yield_wait();
+                if let Some((written,)) = done.get() {
+                    return Ok(written as usize);
+                }
+            }
+        })
+    }
+}
+
+/// The number of bytes that can be queued by writes made while another write is
+/// in progress.
+pub const PENDING_LEN: usize = 64;
+
+/// The size of `PrintWriter`'s buffer, which holds formatted text until it is
+/// written to the console.
+pub const PRINT_BUFFER_LEN: usize = 32;
+
+// Tracks whether a write is in progress, and queues the bytes written by
+// upcalls during it. Tock processes are single-threaded, so the state is a
+// static. Elsewhere (e.g. in unit tests, which run in parallel threads that
+// each have their own fake kernel) it is thread-local.
+mod state {
+    use super::PENDING_LEN;
+    use core::cell::{Cell, RefCell};
+
+    pub struct State {
+        writing: Cell<bool>,
+        pending: RefCell<Pending>,
+    }
+
+    struct Pending {
+        bytes: [u8; PENDING_LEN],
+        len: usize,
+    }
+
+    impl State {
+        const fn new() -> State {
+            State {
+                writing: Cell::new(false),
+                pending: RefCell::new(Pending {
+                    bytes: [0; PENDING_LEN],
+                    len: 0,
+                }),
+            }
+        }
+    }
+
+    #[cfg(target_os = "none")]
+    fn with<R>(f: impl FnOnce(&State) -> R) -> R {
+        static STATE: libtock_platform::SingleThreaded<State> =
+            libtock_platform::SingleThreaded::new(State::new());
+        f(&STATE)
+    }
+
+    #[cfg(not(target_os = "none"))]
+    fn with<R>(f: impl FnOnce(&State) -> R) -> R {
+        std::thread_local! {
+            static STATE: State = State::new();
+        }
+        STATE.with(f)
+    }
+
+    pub fn writing() -> bool {
+        with(|state| state.writing.get())
+    }
+
+    pub fn replace_writing(writing: bool) -> bool {
+        with(|state| state.writing.replace(writing))
+    }
+
+    // Appends bytes to the queue, dropping those that do not fit.
+    pub fn queue(bytes: &[u8]) {
+        with(|state| {
+            let mut pending = state.pending.borrow_mut();
+            let start = pending.len;
+            let len = bytes.len().min(PENDING_LEN - start);
+            pending.bytes[start..start + len].copy_from_slice(&bytes[..len]);
+            pending.len += len;
+        })
+    }
+
+    // Moves the queued bytes into `buffer`, returning their number.
+    pub fn take_pending(buffer: &mut [u8; PENDING_LEN]) -> usize {
+        with(|state| {
+            let mut pending = state.pending.borrow_mut();
+            buffer.copy_from_slice(&pending.bytes);
+            core::mem::replace(&mut pending.len, 0)
+        })
+    }
+}
+
+/// Writes formatted text to the console. Returned by `Console::writer`.
+pub struct ConsoleWriter<S: Syscalls> {
+    syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> ufmt::uWrite for ConsoleWriter<S> {
+    type Error = ErrorCode;
+
+    fn write_str(&mut self, s: &str) -> Result<(), ErrorCode> {
+        Console::<S>::write(s.as_bytes())
+    }
+}
+
+impl<S: Syscalls> core::fmt::Write for ConsoleWriter<S> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        Console::<S>::write(s.as_bytes()).map_err(|_| core::fmt::Error)
+    }
+}
+
+/// Formats text for a print. Returned by `Console::print_writer`.
+pub struct PrintWriter<S: Syscalls> {
+    // True if this print started while another write was in progress.
+    nested: bool,
+    buffer: [u8; PRINT_BUFFER_LEN],
+    len: usize,
+    syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> PrintWriter<S> {
+    // Writes the buffered text to the console, or queues it if this print is
+    // nested.
+    fn flush(&mut self) -> Result<(), ErrorCode> {
+        let len = core::mem::replace(&mut self.len, 0);
+        match self.nested {
+            false => Console::<S>::write_all(&self.buffer[..len]),
+            true => {
+                state::queue(&self.buffer[..len]);
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<S: Syscalls> ufmt::uWrite for PrintWriter<S> {
+    type Error = ErrorCode;
+
+    fn write_str(&mut self, s: &str) -> Result<(), ErrorCode> {
+        let mut bytes = s.as_bytes();
+        while !bytes.is_empty() {
+            if self.len == PRINT_BUFFER_LEN {
+                self.flush()?;
+            }
+            let len = bytes.len().min(PRINT_BUFFER_LEN - self.len);
+            self.buffer[self.len..self.len + len].copy_from_slice(&bytes[..len]);
+            self.len += len;
+            bytes = &bytes[len..];
+        }
+        Ok(())
+    }
+}
+
+impl<S: Syscalls> core::fmt::Write for PrintWriter<S> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        ufmt::uWrite::write_str(self, s).map_err(|_| core::fmt::Error)
+    }
+}
+
+impl<S: Syscalls> Drop for PrintWriter<S> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+        if self.nested {
+            return;
+        }
+        Console::<S>::write_pending();
+        state::replace_writing(false);
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 1;
+
+// Command IDs
+const EXISTS: u32 = 0;
+const WRITE: u32 = 1;
+const READ: u32 = 2;
+
+mod subscribe {
+    pub const WRITE: u32 = 1;
+    pub const READ: u32 = 2;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 1;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 1;
+}
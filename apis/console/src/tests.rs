@@ -0,0 +1,188 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+use std::vec::Vec;
+
+type Console = super::Console<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(!Console::exists());
+    assert_eq!(Console::write(b"Hello"), Err(ErrorCode::NoDevice));
+    assert_eq!(Console::read(&mut [0; 4]), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    assert!(Console::exists());
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Console::write(b""), Ok(()));
+    assert_eq!(Console::write(b"Hello, world!"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"Hello, world!");
+}
+
+#[test]
+fn write_chunks() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    driver.set_max_write_len(4);
+    assert_eq!(Console::write(b"Hello, world!"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"Hello, world!");
+
+    // A kernel that makes no progress results in an error rather than an
+    // infinite loop.
+    driver.set_max_write_len(0);
+    assert_eq!(Console::write(b"Hello"), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn writing() {
+    use super::{subscribe::READ, DRIVER_NUM};
+    use libtock_platform::subscribe::FnUpcall;
+    use libtock_platform::{share, DefaultConfig, Subscribe, Syscalls};
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    assert!(!Console::writing());
+    // Runs an upcall while the write waits for the kernel.
+    let writing_in_upcall = core::cell::Cell::new(None);
+    let upcall = FnUpcall(|_, _, _| writing_in_upcall.set(Some(Console::writing())));
+    share::scope::<Subscribe<_, DRIVER_NUM, READ>, _, _>(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, READ>(subscribe, &upcall)
+            .unwrap();
+        libtock_unittest::upcall::schedule(DRIVER_NUM, READ, (0, 0, 0)).unwrap();
+        assert_eq!(Console::write(b"Hello"), Ok(()));
+    });
+    assert_eq!(writing_in_upcall.get(), Some(true));
+    assert!(!Console::writing());
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    driver.push_input(b"abcdef");
+    let mut buffer = [0; 4];
+    assert_eq!(Console::read(&mut buffer), Ok(4));
+    assert_eq!(buffer, *b"abcd");
+    assert_eq!(Console::read(&mut buffer), Ok(2));
+    assert_eq!(buffer[..2], *b"ef");
+}
+
+#[test]
+fn writer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let mut writer = Console::writer();
+    assert_eq!(ufmt::uwrite!(writer, "{} + {} = {}", 1, 2, 3u8), Ok(()));
+    assert_eq!(driver.take_bytes(), b"1 + 2 = 3");
+    assert_eq!(
+        core::fmt::Write::write_fmt(&mut writer, format_args!("{:x}", 255)),
+        Ok(())
+    );
+    assert_eq!(driver.take_bytes(), b"ff");
+}
+
+#[test]
+fn print_writer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let mut writer = Console::print_writer();
+    // Longer than PRINT_BUFFER_LEN, so it is written in multiple pieces.
+    assert_eq!(
+        ufmt::uwriteln!(writer, "The quick brown fox jumps over the lazy dog {}", 42),
+        Ok(())
+    );
+    assert!(Console::writing());
+    drop(writer);
+    assert!(!Console::writing());
+    assert_eq!(
+        driver.take_bytes(),
+        b"The quick brown fox jumps over the lazy dog 42\n"
+    );
+}
+
+#[test]
+fn print_writer_nested() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let mut outer = Console::print_writer();
+    assert_eq!(ufmt::uwrite!(outer, "outer "), Ok(()));
+    // A print that starts while another is in progress (as it would in an
+    // upcall) is written after the outer print completes.
+    let mut inner = Console::print_writer();
+    assert_eq!(ufmt::uwriteln!(inner, "inner"), Ok(()));
+    drop(inner);
+    assert_eq!(driver.take_bytes(), b"");
+    assert_eq!(ufmt::uwriteln!(outer, "line"), Ok(()));
+    drop(outer);
+    assert_eq!(driver.take_bytes(), b"outer line\ninner\n");
+
+    // Queued text beyond PENDING_LEN is dropped.
+    let outer = Console::print_writer();
+    let mut inner = Console::print_writer();
+    for _ in 0..super::PENDING_LEN {
+        assert_eq!(ufmt::uwrite!(inner, "ab"), Ok(()));
+    }
+    drop(inner);
+    drop(outer);
+    assert_eq!(
+        driver.take_bytes(),
+        [b'a', b'b'].repeat(super::PENDING_LEN / 2)
+    );
+
+    // Printing works again once the outer print completes.
+    let mut writer = Console::print_writer();
+    assert_eq!(ufmt::uwrite!(writer, "done"), Ok(()));
+    drop(writer);
+    assert_eq!(driver.take_bytes(), b"done");
+}
+
+// Runs `upcall` while Console::write(b"OUTER") waits for the kernel, and
+// returns the bytes written to the console.
+fn write_with_upcall(upcall: impl Fn()) -> Vec<u8> {
+    use super::{subscribe::READ, DRIVER_NUM};
+    use libtock_platform::subscribe::FnUpcall;
+    use libtock_platform::{share, DefaultConfig, Subscribe, Syscalls};
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let upcall = FnUpcall(|_, _, _| upcall());
+    share::scope::<Subscribe<_, DRIVER_NUM, READ>, _, _>(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, READ>(subscribe, &upcall)
+            .unwrap();
+        libtock_unittest::upcall::schedule(DRIVER_NUM, READ, (0, 0, 0)).unwrap();
+        assert_eq!(Console::write(b"OUTER"), Ok(()));
+    });
+    assert!(!Console::writing());
+    driver.take_bytes()
+}
+
+// A print or write from an upcall that interrupts a write is queued, rather
+// than replacing the write's buffer and upcall, and is written once the write
+// completes.
+#[test]
+fn nested_in_write() {
+    let printed = write_with_upcall(|| {
+        let mut writer = Console::print_writer();
+        assert_eq!(ufmt::uwriteln!(writer, " inner {}", 1), Ok(()));
+    });
+    assert_eq!(printed, b"OUTER inner 1\n");
+
+    let written = write_with_upcall(|| assert_eq!(Console::write(b" inner"), Ok(())));
+    assert_eq!(written, b"OUTER inner");
+}
@@ -0,0 +1,233 @@
+//! Decodes frames written by `dwriteln!` back into text. Used by the runner,
+//! and by `dwriteln!` itself when built for the host.
+
+use crate::{BASE64, BOOL, CHAR, SIGNED, STR, UNSIGNED};
+use std::convert::Infallible;
+use std::fmt::Write;
+
+/// An error encountered while decoding a frame.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    /// The frame contains a character that is not part of the base64 alphabet.
+    InvalidCharacter(char),
+
+    /// The frame ended in the middle of a value.
+    Truncated,
+
+    /// The frame's index does not correspond to a format string.
+    UnknownIndex(u32),
+
+    /// An argument has an unknown tag, or a value that is invalid for its tag.
+    InvalidArgument,
+
+    /// The format string contains a placeholder `dwriteln!` does not support.
+    UnsupportedPlaceholder(String),
+
+    /// The frame contains more or fewer arguments than the format string.
+    WrongArgumentCount,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DecodeError::InvalidCharacter(c) => write!(f, "invalid character {:?} in frame", c),
+            DecodeError::Truncated => write!(f, "truncated frame"),
+            DecodeError::UnknownIndex(index) => write!(f, "unknown format string {:#x}", index),
+            DecodeError::InvalidArgument => write!(f, "invalid argument in frame"),
+            DecodeError::UnsupportedPlaceholder(placeholder) => {
+                write!(f, "unsupported placeholder {:?}", placeholder)
+            }
+            DecodeError::WrongArgumentCount => {
+                write!(f, "argument count does not match the format string")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decodes a frame into the line of text it represents. `frame` is the frame's
+/// base64 data, without the frame start character or the trailing newline.
+/// `lookup` returns the format string with the given index.
+pub fn decode_frame<'s>(
+    frame: &str,
+    lookup: impl FnOnce(u32) -> Option<&'s str>,
+) -> Result<String, DecodeError> {
+    let data = decode_base64(frame.trim_end())?;
+    let mut reader = Reader(&data);
+    let index = u32::try_from(reader.leb128()?).map_err(|_| DecodeError::InvalidArgument)?;
+    let fmt = lookup(index).ok_or(DecodeError::UnknownIndex(index))?;
+    let mut line = String::new();
+    let mut rest = fmt;
+    while let Some(position) = rest.find(|c| c == '{' || c == '}') {
+        line.push_str(&rest[..position]);
+        rest = &rest[position..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            line.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let end = match (rest.starts_with('{'), rest.find('}')) {
+            (true, Some(end)) => end,
+            _ => return Err(DecodeError::UnsupportedPlaceholder(rest.into())),
+        };
+        let argument = reader.argument()?;
+        argument.write(&mut line, &rest[1..end])?;
+        rest = &rest[end + 1..];
+    }
+    line.push_str(rest);
+    if !reader.0.is_empty() {
+        return Err(DecodeError::WrongArgumentCount);
+    }
+    Ok(line)
+}
+
+/// Returns the format string with the given index from the contents of the
+/// `.libtock_fmt` section. `address` is the section's address.
+pub fn lookup_fmt(section: &[u8], address: u64, index: u32) -> Option<&str> {
+    let offset = usize::try_from((index as u64).checked_sub(address)?).ok()?;
+    let string = section.get(offset..)?;
+    let len = string.iter().position(|&byte| byte == 0)?;
+    std::str::from_utf8(&string[..len]).ok()
+}
+
+// Implements ufmt::uWrite for a String, so that dwriteln! can encode a frame
+// in memory when built for the host.
+pub(crate) struct StringWriter<'s>(pub &'s mut String);
+
+impl ufmt::uWrite for StringWriter<'_> {
+    type Error = Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Infallible> {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut data = Vec::new();
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    for c in text.chars() {
+        let value = BASE64
+            .iter()
+            .position(|&b| b as char == c)
+            .ok_or(DecodeError::InvalidCharacter(c))?;
+        bits = bits << 6 | value as u32;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            data.push((bits >> bit_count) as u8);
+        }
+    }
+    Ok(data)
+}
+
+enum Argument<'d> {
+    Unsigned(u64),
+    Signed(i64),
+    Str(&'d str),
+    Bool(bool),
+    Char(char),
+}
+
+impl Argument<'_> {
+    // Writes the argument to `line`. `spec` is the part of the placeholder
+    // between the braces, e.g. ":x".
+    fn write(&self, line: &mut String, spec: &str) -> Result<(), DecodeError> {
+        let unsupported = || DecodeError::UnsupportedPlaceholder(format!("{{{}}}", spec));
+        let result = match (spec, self) {
+            ("", Argument::Unsigned(value)) => write!(line, "{}", value),
+            ("", Argument::Signed(value)) => write!(line, "{}", value),
+            ("", Argument::Str(value)) => write!(line, "{}", value),
+            ("", Argument::Bool(value)) => write!(line, "{}", value),
+            ("", Argument::Char(value)) => write!(line, "{}", value),
+            (":?", Argument::Unsigned(value)) => write!(line, "{:?}", value),
+            (":?", Argument::Signed(value)) => write!(line, "{:?}", value),
+            (":?", Argument::Str(value)) => write!(line, "{:?}", value),
+            (":?", Argument::Bool(value)) => write!(line, "{:?}", value),
+            (":?", Argument::Char(value)) => write!(line, "{:?}", value),
+            (_, Argument::Unsigned(value)) => {
+                write_radix(line, spec, *value).ok_or_else(unsupported)?
+            }
+            // The original width is not sent, so negative numbers are printed
+            // in 64-bit two's complement.
+            (_, Argument::Signed(value)) => {
+                write_radix(line, spec, *value as u64).ok_or_else(unsupported)?
+            }
+            _ => return Err(unsupported()),
+        };
+        result.map_err(|_| unsupported())
+    }
+}
+
+// Writes `value` using a {:x}, {:X}, or {:b} placeholder (optionally with #).
+// Returns None if the placeholder is not one of those.
+fn write_radix(line: &mut String, spec: &str, value: u64) -> Option<std::fmt::Result> {
+    Some(match spec {
+        ":x" => write!(line, "{:x}", value),
+        ":#x" => write!(line, "{:#x}", value),
+        ":X" => write!(line, "{:X}", value),
+        ":#X" => write!(line, "{:#X}", value),
+        ":b" => write!(line, "{:b}", value),
+        ":#b" => write!(line, "{:#b}", value),
+        _ => return None,
+    })
+}
+
+// Reads values from a frame's data.
+struct Reader<'d>(&'d [u8]);
+
+impl<'d> Reader<'d> {
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let (&byte, rest) = self.0.split_first().ok_or(DecodeError::Truncated)?;
+        self.0 = rest;
+        Ok(byte)
+    }
+
+    fn leb128(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::InvalidArgument)
+    }
+
+    fn argument(&mut self) -> Result<Argument<'d>, DecodeError> {
+        let tag = match self.byte() {
+            Ok(tag) => tag,
+            Err(_) => return Err(DecodeError::WrongArgumentCount),
+        };
+        Ok(match tag {
+            UNSIGNED => Argument::Unsigned(self.leb128()?),
+            SIGNED => {
+                let value = self.leb128()?;
+                Argument::Signed((value >> 1) as i64 ^ -((value & 1) as i64))
+            }
+            STR => {
+                let len = usize::try_from(self.leb128()?).map_err(|_| DecodeError::Truncated)?;
+                if self.0.len() < len {
+                    return Err(DecodeError::Truncated);
+                }
+                let (bytes, rest) = self.0.split_at(len);
+                self.0 = rest;
+                Argument::Str(std::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidArgument)?)
+            }
+            BOOL => match self.byte()? {
+                0 => Argument::Bool(false),
+                1 => Argument::Bool(true),
+                _ => return Err(DecodeError::InvalidArgument),
+            },
+            CHAR => {
+                let value =
+                    u32::try_from(self.leb128()?).map_err(|_| DecodeError::InvalidArgument)?;
+                Argument::Char(char::from_u32(value).ok_or(DecodeError::InvalidArgument)?)
+            }
+            _ => return Err(DecodeError::InvalidArgument),
+        })
+    }
+}
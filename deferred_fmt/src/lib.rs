@@ -0,0 +1,348 @@
+//! `libtock_deferred_fmt` prints formatted lines without storing their format
+//! strings in the process binary. `dwriteln!` places its format string in the
+//! `.libtock_fmt` ELF section, which is not loaded onto the board. Instead of
+//! formatting the line, it writes a frame containing the format string's index
+//! (its address within `.libtock_fmt`) and the encoded arguments. The runner
+//! decodes frames back into text using the ELF file it deployed:
+//! ```ignore
+//! use libtock_deferred_fmt::dwriteln;
+//!
+//! dwriteln!(Console::writer(), "Button {} is {}", index, "pressed")?;
+//! ```
+//! Arguments must implement `Format`, which is implemented for integers,
+//! `bool`, `char`, and `str`. Format strings support the `{}`, `{:?}`, `{:x}`,
+//! `{:X}`, and `{:b}` placeholders (the latter three with an optional `#`), as
+//! well as `{{` and `}}` escapes. The number of arguments is checked at compile
+//! time.
+//!
+//! A frame is a line of text: the `FRAME_START` character, followed by the
+//! base64 encoding (without padding) of the frame's data, followed by `\n`.
+//! The frame's data is the index followed by the arguments, each of which is a
+//! tag byte followed by its value. Integers are LEB128-encoded (signed integers
+//! are zigzag-encoded first), and strings are their LEB128-encoded length
+//! followed by their bytes.
+//!
+//! When built for the host (e.g. for `libtock_host_runtime`), `dwriteln!`
+//! writes the formatted line instead, as there is no runner to decode it.
+//! Process binaries that use `dwriteln!` cannot be position-independent, as the
+//! index is computed from the format string's link-time address. `dwriteln!`
+//! is a compile error when the `pic` feature is enabled.
+
+#![cfg_attr(target_os = "none", no_std)]
+
+#[cfg(not(target_os = "none"))]
+pub mod decode;
+
+use ufmt::uWrite;
+
+/// The character that starts a frame.
+pub const FRAME_START: char = '\x1e';
+
+// Argument tags.
+const UNSIGNED: u8 = 0;
+const SIGNED: u8 = 1;
+const STR: u8 = 2;
+const BOOL: u8 = 3;
+const CHAR: u8 = 4;
+
+/// Writes a line to a `ufmt::uWrite` writer (such as the console), deferring
+/// the formatting to the runner. Returns the writer's error if writing fails.
+/// See the crate documentation for details.
+#[cfg(not(feature = "pic"))]
+#[macro_export]
+macro_rules! dwriteln {
+    ($writer:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {{
+        const _: () = assert!(
+            $crate::count_arguments($fmt) == <[&str]>::len(&[$(stringify!($arg)),*]),
+            "the number of arguments does not match the format string"
+        );
+        // On Tock, the format string is only stored in .libtock_fmt, which is
+        // not loaded, so only its address may be used.
+        #[cfg(target_os = "none")]
+        let fmt = {
+            const STRING: &str = concat!($fmt, "\0");
+            #[link_section = ".libtock_fmt"]
+            static FMT: [u8; STRING.len()] = $crate::__str_to_array(STRING);
+            ::core::ptr::addr_of!(FMT) as usize
+        };
+        #[cfg(not(target_os = "none"))]
+        let fmt = $fmt;
+        $crate::write_frame(&mut $writer, fmt, ($(&$arg,)*))
+    }};
+}
+
+/// Writes a line to a `ufmt::uWrite` writer (such as the console), deferring
+/// the formatting to the runner. Returns the writer's error if writing fails.
+/// See the crate documentation for details.
+#[cfg(feature = "pic")]
+#[macro_export]
+macro_rules! dwriteln {
+    ($($tokens:tt)*) => {
+        compile_error!(
+            "dwriteln! is not supported in position-independent process binaries, \
+             as the index is computed from the format string's link-time address"
+        )
+    };
+}
+
+/// Returns the number of arguments a format string requires. Used by
+/// `dwriteln!` to check its arguments.
+pub const fn count_arguments(fmt: &str) -> usize {
+    let fmt = fmt.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+    while i < fmt.len() {
+        if (fmt[i] == b'{' || fmt[i] == b'}') && i + 1 < fmt.len() && fmt[i + 1] == fmt[i] {
+            i += 2;
+            continue;
+        }
+        if fmt[i] == b'{' {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+// Converts a string into a byte array, for use in dwriteln!.
+#[doc(hidden)]
+pub const fn __str_to_array<const N: usize>(string: &str) -> [u8; N] {
+    let bytes = string.as_bytes();
+    let mut array = [0; N];
+    let mut i = 0;
+    while i < N {
+        array[i] = bytes[i];
+        i += 1;
+    }
+    array
+}
+
+/// A value that can be sent as an argument of `dwriteln!`.
+pub trait Format {
+    fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>);
+}
+
+/// Encodes a frame, writing it to a `ufmt::uWrite` writer as it goes.
+pub struct Encoder<'w, W: uWrite + ?Sized> {
+    writer: &'w mut W,
+
+    // The first error returned by the writer. Once an error has occurred,
+    // nothing more is written.
+    error: Option<W::Error>,
+
+    // Bits that have not yet been base64-encoded. Only the low `bit_count` bits
+    // are used.
+    bits: u32,
+    bit_count: u32,
+
+    // Base64 characters that have not yet been written. The writer may be slow
+    // (the console makes several system calls per write), so the characters
+    // are written in batches.
+    buffer: [u8; 32],
+    buffer_len: usize,
+}
+
+impl<'w, W: uWrite + ?Sized> Encoder<'w, W> {
+    /// Encodes an unsigned integer.
+    pub fn encode_unsigned(&mut self, value: u64) {
+        self.encode_byte(UNSIGNED);
+        self.encode_leb128(value);
+    }
+
+    /// Encodes a signed integer.
+    pub fn encode_signed(&mut self, value: i64) {
+        self.encode_byte(SIGNED);
+        self.encode_leb128(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    /// Encodes a string.
+    pub fn encode_str(&mut self, value: &str) {
+        self.encode_byte(STR);
+        self.encode_leb128(value.len() as u64);
+        for &byte in value.as_bytes() {
+            self.encode_byte(byte);
+        }
+    }
+
+    /// Encodes a boolean.
+    pub fn encode_bool(&mut self, value: bool) {
+        self.encode_byte(BOOL);
+        self.encode_byte(value as u8);
+    }
+
+    /// Encodes a character.
+    pub fn encode_char(&mut self, value: char) {
+        self.encode_byte(CHAR);
+        self.encode_leb128(value as u64);
+    }
+
+    fn new(writer: &'w mut W) -> Self {
+        Self {
+            writer,
+            error: None,
+            bits: 0,
+            bit_count: 0,
+            buffer: [0; 32],
+            buffer_len: 0,
+        }
+    }
+
+    fn encode_leb128(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.encode_byte(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.encode_byte(value as u8);
+    }
+
+    fn encode_byte(&mut self, byte: u8) {
+        self.bits = self.bits << 8 | byte as u32;
+        self.bit_count += 8;
+        while self.bit_count >= 6 {
+            self.bit_count -= 6;
+            self.push_char(self.bits >> self.bit_count);
+        }
+    }
+
+    // Appends the base64 character for the low 6 bits of `bits` to the buffer.
+    fn push_char(&mut self, bits: u32) {
+        if self.buffer_len == self.buffer.len() {
+            self.flush();
+        }
+        self.buffer[self.buffer_len] = BASE64[(bits & 0x3f) as usize];
+        self.buffer_len += 1;
+    }
+
+    fn flush(&mut self) {
+        if self.error.is_none() {
+            // Safety: The buffer only contains base64 characters, which are
+            // ASCII.
+            let chars = unsafe { core::str::from_utf8_unchecked(&self.buffer[..self.buffer_len]) };
+            if let Err(error) = self.writer.write_str(chars) {
+                self.error = Some(error);
+            }
+        }
+        self.buffer_len = 0;
+    }
+
+    // Writes the remaining bits (padded with zeros) and characters, and returns
+    // the first error the writer returned.
+    fn finish(mut self) -> Result<(), W::Error> {
+        if self.bit_count > 0 {
+            self.push_char(self.bits << (6 - self.bit_count));
+        }
+        self.flush();
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The arguments of `dwriteln!`, as a tuple of references to values that
+/// implement `Format`.
+pub trait Arguments {
+    fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>);
+}
+
+// Writes a frame to `writer`. Called by dwriteln!. `fmt` is the address of the
+// format string (followed by a nul byte) in the .libtock_fmt section.
+#[doc(hidden)]
+#[cfg(target_os = "none")]
+pub fn write_frame<W: uWrite + ?Sized, A: Arguments>(
+    writer: &mut W,
+    fmt: usize,
+    arguments: A,
+) -> Result<(), W::Error> {
+    encode_frame(writer, fmt as u32, arguments)?;
+    writer.write_str("\n")
+}
+
+// Writes the formatted line to `writer`. Called by dwriteln!.
+#[doc(hidden)]
+#[cfg(not(target_os = "none"))]
+pub fn write_frame<W: uWrite + ?Sized, A: Arguments>(
+    writer: &mut W,
+    fmt: &str,
+    arguments: A,
+) -> Result<(), W::Error> {
+    let mut frame = String::new();
+    let _ = encode_frame(&mut decode::StringWriter(&mut frame), 0, arguments);
+    let line = decode::decode_frame(&frame[1..], |_| Some(fmt))
+        .unwrap_or_else(|error| format!("<{}>", error));
+    writer.write_str(&line)?;
+    writer.write_str("\n")
+}
+
+// Writes the frame start character and the encoded index and arguments.
+fn encode_frame<W: uWrite + ?Sized, A: Arguments>(
+    writer: &mut W,
+    index: u32,
+    arguments: A,
+) -> Result<(), W::Error> {
+    let mut buffer = [0; 4];
+    writer.write_str(FRAME_START.encode_utf8(&mut buffer))?;
+    let mut encoder = Encoder::new(writer);
+    encoder.encode_leb128(index as u64);
+    arguments.format(&mut encoder);
+    encoder.finish()
+}
+
+impl<T: Format + ?Sized> Format for &T {
+    fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>) {
+        (**self).format(encoder)
+    }
+}
+
+macro_rules! format_impls {
+    ($method:ident, $cast:ty: $($type:ty),*) => {$(
+        impl Format for $type {
+            fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>) {
+                encoder.$method(*self as $cast)
+            }
+        }
+    )*};
+}
+
+format_impls!(encode_unsigned, u64: u8, u16, u32, u64, usize);
+format_impls!(encode_signed, i64: i8, i16, i32, i64, isize);
+format_impls!(encode_bool, bool: bool);
+format_impls!(encode_char, char: char);
+
+impl Format for str {
+    fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>) {
+        encoder.encode_str(self)
+    }
+}
+
+// Implements Arguments for tuples of the given names' length.
+macro_rules! arguments_impl {
+    ($($name:ident),*) => {
+        impl<$($name: Format),*> Arguments for ($($name,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn format<W: uWrite + ?Sized>(&self, encoder: &mut Encoder<W>) {
+                let ($($name,)*) = self;
+                $($name.format(encoder);)*
+            }
+        }
+    };
+}
+
+arguments_impl!();
+arguments_impl!(A);
+arguments_impl!(A, B);
+arguments_impl!(A, B, C);
+arguments_impl!(A, B, C, D);
+arguments_impl!(A, B, C, D, E);
+arguments_impl!(A, B, C, D, E, F);
+arguments_impl!(A, B, C, D, E, F, G);
+arguments_impl!(A, B, C, D, E, F, G, H);
+arguments_impl!(A, B, C, D, E, F, G, H, I);
+arguments_impl!(A, B, C, D, E, F, G, H, I, J);
+arguments_impl!(A, B, C, D, E, F, G, H, I, J, K);
+arguments_impl!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+use super::decode::{decode_frame, lookup_fmt, DecodeError, StringWriter};
+use super::{count_arguments, encode_frame, FRAME_START};
+
+// Encodes a frame with the given index and arguments, returning the frame's
+// base64 data.
+fn encode<A: super::Arguments>(index: u32, arguments: A) -> String {
+    let mut frame = String::new();
+    let _ = encode_frame(&mut StringWriter(&mut frame), index, arguments);
+    assert!(frame.starts_with(FRAME_START));
+    frame[1..].to_string()
+}
+
+fn round_trip<A: super::Arguments>(fmt: &str, arguments: A) -> Result<String, DecodeError> {
+    decode_frame(&encode(7, arguments), |index| {
+        assert_eq!(index, 7);
+        Some(fmt)
+    })
+}
+
+#[test]
+fn arguments() {
+    assert_eq!(round_trip("Hello", ()).unwrap(), "Hello");
+    assert_eq!(
+        round_trip("{} {} {} {}", (&0u8, &u32::MAX, &u64::MAX, &usize::MAX)).unwrap(),
+        format!("0 {} {} {}", u32::MAX, u64::MAX, usize::MAX)
+    );
+    assert_eq!(
+        round_trip("{} {} {} {}", (&-1i8, &i16::MIN, &i32::MAX, &i64::MIN)).unwrap(),
+        format!("-1 {} {} {}", i16::MIN, i32::MAX, i64::MIN)
+    );
+    assert_eq!(
+        round_trip("{} {:?} {} {:?}", (&"str", &"str", &true, &'c')).unwrap(),
+        "str \"str\" true 'c'"
+    );
+    assert_eq!(
+        round_trip("{:x} {:#X} {:b} {:#b}", (&255u8, &255u8, &5u8, &5u8)).unwrap(),
+        "ff 0xFF 101 0b101"
+    );
+    assert_eq!(round_trip("{{{}}} }}", (&'é',)).unwrap(), "{é} }");
+    // Exercise every string length modulo 3, which covers each base64 padding
+    // case.
+    for len in 0..6 {
+        let string = "abcdef".get(..len).unwrap();
+        assert_eq!(round_trip("{}", (&string,)).unwrap(), string);
+    }
+}
+
+#[test]
+fn decode_errors() {
+    assert_eq!(
+        round_trip("{} {}", (&1u8,)),
+        Err(DecodeError::WrongArgumentCount)
+    );
+    assert_eq!(
+        round_trip("{}", (&1u8, &2u8)),
+        Err(DecodeError::WrongArgumentCount)
+    );
+    assert_eq!(
+        round_trip("{:x}", (&"str",)),
+        Err(DecodeError::UnsupportedPlaceholder("{:x}".into()))
+    );
+    assert_eq!(
+        round_trip("{:>4}", (&1u8,)),
+        Err(DecodeError::UnsupportedPlaceholder("{:>4}".into()))
+    );
+    assert_eq!(
+        round_trip("} {}", (&1u8,)),
+        Err(DecodeError::UnsupportedPlaceholder("} {}".into()))
+    );
+    assert_eq!(
+        decode_frame(&encode(3, ()), |_| None),
+        Err(DecodeError::UnknownIndex(3))
+    );
+    assert_eq!(
+        decode_frame("A!", |_| Some("")),
+        Err(DecodeError::InvalidCharacter('!'))
+    );
+    // An index with its continuation bit set, and nothing after it.
+    assert_eq!(
+        decode_frame("gA", |_| Some("")),
+        Err(DecodeError::Truncated)
+    );
+}
+
+#[test]
+fn lookup() {
+    let section = b"\0first\0second {}\0unterminated";
+    assert_eq!(lookup_fmt(section, 0x100, 0x101), Some("first"));
+    assert_eq!(lookup_fmt(section, 0x100, 0x107), Some("second {}"));
+    assert_eq!(lookup_fmt(section, 0x100, 0xff), None);
+    assert_eq!(lookup_fmt(section, 0x100, 0x111), None);
+    assert_eq!(lookup_fmt(section, 0x100, 0x200), None);
+}
+
+#[test]
+fn argument_count() {
+    assert_eq!(count_arguments(""), 0);
+    assert_eq!(count_arguments("{} {:x} {:?}"), 3);
+    assert_eq!(count_arguments("{{}} {{{}}}"), 1);
+}
+
+// On the host, dwriteln! writes the formatted line.
+#[test]
+fn host_dwriteln() {
+    let mut line = String::new();
+    let mut writer = StringWriter(&mut line);
+    assert_eq!(
+        crate::dwriteln!(writer, "{} + {} = {:#x}", 1u8, 2u8, 3u8),
+        Ok(())
+    );
+    assert_eq!(crate::dwriteln!(writer, "done"), Ok(()));
+    assert_eq!(line, "1 + 2 = 0x3\ndone\n");
+}
@@ -0,0 +1,72 @@
+//! Host implementation of `init_fn!`.
+
+/// Registers a function with the signature `fn()` to run before the process
+/// binary's `main`. It has the same interface as `libtock_runtime::init_fn!`.
+///
+/// # Example
+/// ```ignore
+/// fn init_logger() { /* Omitted */ }
+/// libtock_host_runtime::init_fn!{init_logger, priority = 200}
+/// ```
+// The host's linker does not sort sections by priority, so init_fn! places an
+// InitFn (which records the priority) in the libtock_init section, and run
+// sorts them.
+#[macro_export]
+macro_rules! init_fn {
+    {$name:path} => {
+        $crate::init_fn!{$name, priority = 1000}
+    };
+    {$name:path, priority = $priority:literal} => {
+        const _: () = {
+            #[link_section = "libtock_init"]
+            #[used]
+            static INIT_FN: $crate::InitFn = $crate::InitFn {
+                priority: $priority,
+                function: $name,
+            };
+        };
+    };
+}
+
+#[doc(hidden)]
+pub struct InitFn {
+    pub priority: u16,
+    pub function: fn(),
+}
+
+// The libtock_init section must be non-empty, as otherwise the linker does
+// not define __start_libtock_init and __stop_libtock_init.
+#[link_section = "libtock_init"]
+#[used]
+static PLACEHOLDER: InitFn = InitFn {
+    priority: 0,
+    function: || {},
+};
+
+// Runs the functions registered with init_fn!, in priority order. Called by
+// start.
+pub(crate) fn run() {
+    extern "Rust" {
+        // Defined by the linker.
+        static __start_libtock_init: InitFn;
+        static __stop_libtock_init: InitFn;
+    }
+    // Referencing PLACEHOLDER makes sure the linker includes it.
+    let placeholder: *const InitFn = &PLACEHOLDER;
+    // Safety: The linker places every InitFn in the libtock_init section
+    // between __start_libtock_init and __stop_libtock_init.
+    let mut init_fns = unsafe {
+        let start: *const InitFn = core::ptr::addr_of!(__start_libtock_init);
+        let end: *const InitFn = core::ptr::addr_of!(__stop_libtock_init);
+        core::slice::from_raw_parts(start, end.offset_from(start) as usize)
+    }
+    .iter()
+    .filter(|init_fn| !core::ptr::eq(*init_fn, placeholder))
+    .collect::<Vec<_>>();
+    // sort_by_key is stable, so functions with the same priority run in link
+    // order.
+    init_fns.sort_by_key(|init_fn| init_fn.priority);
+    for init_fn in init_fns {
+        (init_fn.function)();
+    }
+}
@@ -0,0 +1,69 @@
+//! Reads simulated hardware events from stdin. A separate thread reads stdin,
+//! and sends the events it parses to the process' thread, which applies them to
+//! the fake drivers when the process yields.
+
+use std::io::BufRead;
+use std::sync::mpsc::{channel, Receiver};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    Button { index: u32, pressed: bool },
+    ConsoleInput(Vec<u8>),
+}
+
+/// Parses a line of input into the events it describes. Empty lines describe no
+/// events.
+pub fn parse_line(line: &str) -> Result<Vec<Event>, String> {
+    let mut words = line.split_whitespace();
+    let command = match words.next() {
+        None => return Ok(Vec::new()),
+        Some(command) => command,
+    };
+    // The rest of the line (after a single separating space) is console input.
+    if command == "console" {
+        let text = line.trim_start()["console".len()..]
+            .strip_prefix(' ')
+            .unwrap_or_default();
+        return Ok(vec![Event::ConsoleInput(
+            format!("{}\n", text).into_bytes(),
+        )]);
+    }
+    let index = match (words.next().map(str::parse), words.next()) {
+        (Some(Ok(index)), None) => index,
+        _ => return Err(format!("Invalid input {:?}: {}", line, USAGE)),
+    };
+    let button = |pressed| Event::Button { index, pressed };
+    match command {
+        "press" => Ok(vec![button(true)]),
+        "release" => Ok(vec![button(false)]),
+        "click" => Ok(vec![button(true), button(false)]),
+        _ => Err(format!("Unknown command {:?}: {}", command, USAGE)),
+    }
+}
+
+const USAGE: &str = "expected press <n>, release <n>, click <n>, or console <text>";
+
+/// Spawns the thread that reads stdin. The returned receiver is disconnected
+/// when stdin is closed.
+pub fn spawn_reader() -> Receiver<Event> {
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => return,
+            };
+            match parse_line(&line) {
+                Ok(events) => {
+                    for event in events {
+                        if sender.send(event).is_err() {
+                            return;
+                        }
+                    }
+                }
+                Err(message) => eprintln!("{}", message),
+            }
+        }
+    });
+    receiver
+}
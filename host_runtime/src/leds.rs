@@ -0,0 +1,47 @@
+//! Wraps `fake::Leds` to print LED changes to stdout.
+
+use crate::NUM_LEDS;
+use libtock_platform::CommandReturn;
+use libtock_unittest::fake::{self, SyscallDriver};
+use std::rc::Rc;
+
+pub struct Leds {
+    leds: Rc<fake::Leds<NUM_LEDS>>,
+}
+
+impl Leds {
+    pub fn new() -> Rc<Leds> {
+        Rc::new(Leds {
+            leds: fake::Leds::new(),
+        })
+    }
+
+    // Returns the state of every LED.
+    fn states(&self) -> [bool; NUM_LEDS] {
+        let mut states = [false; NUM_LEDS];
+        for (led, state) in states.iter_mut().enumerate() {
+            *state = self.leds.get_led(led as u32) == Some(true);
+        }
+        states
+    }
+}
+
+impl SyscallDriver for Leds {
+    fn id(&self) -> u32 {
+        self.leds.id()
+    }
+    fn num_upcalls(&self) -> u32 {
+        self.leds.num_upcalls()
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, argument1: u32) -> CommandReturn {
+        let before = self.states();
+        let command_return = self.leds.command(command_num, argument0, argument1);
+        for (led, (before, after)) in before.iter().zip(self.states()).enumerate() {
+            if *before != after {
+                println!("LED {}: {}", led, if after { "on" } else { "off" });
+            }
+        }
+        command_return
+    }
+}
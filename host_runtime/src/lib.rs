@@ -0,0 +1,59 @@
+//! `libtock_host_runtime` runs Tock process binaries as native host (e.g.
+//! Linux) processes, which allows quick iteration without Tock hardware or
+//! QEMU. It provides the same interface as `libtock_runtime` (`TockSyscalls`,
+//! `set_main!`, `stack_size!`, `init_fn!`, and the TBF option macros), and is used by
+//! `libtock2` in place of `libtock_runtime` when `libtock2` is built for the
+//! host with its `host` feature enabled:
+//! ```shell
+//! cargo run -p libtock2 --features=host --example leds
+//! ```
+//!
+//! Process binaries are `#![no_main]`, so `set_main!` also defines the host's
+//! C `main` function, which sets up a `libtock_unittest::fake::Kernel` and then
+//! calls the process binary's `main`. System calls are routed to the fake
+//! kernel, which has the following fake drivers attached:
+//! - LEDs (`NUM_LEDS` of them). LED changes are printed to stdout.
+//! - Buttons (`NUM_BUTTONS` of them). Button events are read from stdin, one
+//!   per line: `press <n>`, `release <n>`, or `click <n>` (a press followed by
+//!   a release).
+//! - Console. Written bytes are printed to stdout. Input lines of the form
+//!   `console <text>` provide `<text>` (followed by a newline) as console
+//!   input.
+//! - LowLevelDebug. Messages are printed to stdout.
+//!
+//! Upcalls are delivered when the process yields. When the process waits for
+//! an upcall that has not been scheduled, the runtime waits for input on
+//! stdin. If stdin is closed, no upcall can be scheduled, so the process
+//! exits. Memop's brk and sbrk operations are backed by a `HEAP_SIZE` byte
+//! buffer, and the exit system call exits the host process with the completion
+//! code as its exit status.
+
+#![warn(unsafe_op_in_unsafe_fn)]
+
+mod init;
+mod input;
+mod leds;
+mod startup;
+mod syscalls_impl;
+mod tbf;
+
+#[doc(hidden)]
+pub use init::InitFn;
+#[doc(hidden)]
+pub use startup::{exit_hook, start};
+
+/// TockSyscalls implements `libtock_platform::Syscalls` by forwarding system
+/// calls to the host's fake kernel.
+pub struct TockSyscalls;
+
+/// The number of LEDs the host provides.
+pub const NUM_LEDS: usize = 4;
+
+/// The number of buttons the host provides.
+pub const NUM_BUTTONS: usize = 4;
+
+/// The size of the memory available to Memop's brk and sbrk operations.
+pub const HEAP_SIZE: usize = 0x10000;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,114 @@
+//! Runtime components related to process startup.
+
+use crate::syscalls_impl::Host;
+use crate::{HEAP_SIZE, NUM_BUTTONS};
+use libtock_unittest::fake;
+
+/// `set_main!` is used to tell `libtock_host_runtime` where the process
+/// binary's `main` function is. It has the same interface as
+/// `libtock_runtime::set_main!`, and additionally defines the host's C `main`
+/// function, which calls `start`.
+///
+/// # Example
+/// ```ignore
+/// libtock_host_runtime::set_main!{main};
+///
+/// fn main() -> () { /* Omitted */ }
+/// ```
+// Like libtock_runtime's set_main!, this generates libtock_unsafe_main, which
+// is called by start.
+#[macro_export]
+macro_rules! set_main {
+    {async $name:ident} => {
+        #[no_mangle]
+        fn libtock_unsafe_main() -> ! {
+            use $crate::TockSyscalls;
+            let res = libtock_platform::future::block_on::<TockSyscalls, _>($name());
+            #[allow(unreachable_code)] // so that async fn main() -> ! does not produce a warning.
+            {
+                $crate::exit_hook();
+                libtock_platform::Termination::complete::<TockSyscalls>(res)
+            }
+        }
+        $crate::__host_main!{}
+    };
+    {$name:ident} => {
+        #[no_mangle]
+        fn libtock_unsafe_main() -> ! {
+            use $crate::TockSyscalls;
+            let res = $name();
+            #[allow(unreachable_code)] // so that fn main() -> ! does not produce a warning.
+            {
+                $crate::exit_hook();
+                libtock_platform::Termination::complete::<TockSyscalls>(res)
+            }
+        }
+        $crate::__host_main!{}
+    }
+}
+
+// Defines the C main function. Process binaries are #![no_main], so the host's
+// C runtime calls this directly.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __host_main {
+    {} => {
+        #[export_name = "main"]
+        extern "C" fn libtock_host_main(_argc: i32, _argv: *const *const u8) -> i32 {
+            $crate::start()
+        }
+    }
+}
+
+/// Accepted for compatibility with `libtock_runtime::stack_size!`. Host
+/// processes use the host's stack, so the size is ignored.
+#[macro_export]
+macro_rules! stack_size {
+    {$size:expr} => {
+        const _: usize = $size;
+    }
+}
+
+/// Called by `libtock_unsafe_main` after `main` returns, before the process
+/// exits.
+#[doc(hidden)]
+pub fn exit_hook() {}
+
+/// Sets up the fake kernel and its drivers, then calls `libtock_unsafe_main`.
+/// Called by the C main function defined by `set_main!`.
+#[doc(hidden)]
+pub fn start() -> ! {
+    let kernel = fake::Kernel::new();
+
+    // The heap is never freed, as the process never returns.
+    let heap = Box::leak(vec![0u8; HEAP_SIZE].into_boxed_slice()).as_mut_ptr_range();
+    kernel.set_memory_layout(fake::MemoryLayout {
+        ram_start: heap.start,
+        ram_end: heap.end,
+        grant_region_start: heap.end,
+        ..Default::default()
+    });
+
+    kernel.add_driver(&crate::leds::Leds::new());
+    let buttons = fake::Buttons::<NUM_BUTTONS>::new();
+    kernel.add_driver(&buttons);
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    kernel.add_driver(&fake::LowLevelDebug::new());
+
+    crate::syscalls_impl::init(Host {
+        kernel,
+        buttons,
+        console,
+        events: crate::input::spawn_reader(),
+    });
+
+    crate::init::run();
+
+    extern "Rust" {
+        fn libtock_unsafe_main() -> !;
+    }
+    unsafe {
+        libtock_unsafe_main();
+    }
+}
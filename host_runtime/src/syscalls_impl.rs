@@ -0,0 +1,138 @@
+//! Implements `RawSyscalls` for the host's `TockSyscalls`. Most system calls
+//! are forwarded to `fake::Syscalls` unchanged. Yield additionally applies
+//! input events to the fake drivers, and waits for input when the process
+//! waits for an upcall that has not been scheduled. Exit exits the host
+//! process.
+
+use crate::input::Event;
+use crate::NUM_BUTTONS;
+use libtock_platform::{exit_id, syscall_class, yield_id, RawSyscalls, Register};
+use libtock_unittest::fake;
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::io::Write;
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+
+/// The host's state, which is created by `start` and lives for the rest of the
+/// process.
+pub struct Host {
+    pub kernel: fake::Kernel,
+    pub buttons: Rc<fake::Buttons<NUM_BUTTONS>>,
+    pub console: Rc<fake::Console>,
+    pub events: Receiver<Event>,
+}
+
+thread_local!(static HOST: RefCell<Option<Host>> = RefCell::new(None));
+
+pub fn init(host: Host) {
+    HOST.with(|cell| cell.replace(Some(host)));
+}
+
+// Convenience function to access HOST. Note that upcalls may make system calls,
+// so f must not invoke upcalls.
+fn with_host<F: FnOnce(&Host) -> R, R>(f: F) -> R {
+    HOST.with(|cell| f(cell.borrow().as_ref().expect("No host runtime exists")))
+}
+
+unsafe impl RawSyscalls for crate::TockSyscalls {
+    unsafe fn yield1([r0]: [Register; 1]) {
+        if u32::try_from(r0) == Ok(yield_id::WAIT) {
+            wait_until(|kernel| kernel.is_upcall_queued());
+        }
+        unsafe { fake::Syscalls::yield1([r0]) }
+    }
+
+    unsafe fn yield2([r0, r1]: [Register; 2]) {
+        apply_next_event();
+        unsafe { fake::Syscalls::yield2([r0, r1]) }
+    }
+
+    unsafe fn yield3([r0, r1, r2]: [Register; 3]) -> [Register; 3] {
+        if u32::try_from(r0) == Ok(yield_id::WAIT_FOR) {
+            if let (Ok(driver_num), Ok(subscribe_num)) = (u32::try_from(r1), u32::try_from(r2)) {
+                wait_until(|kernel| kernel.is_upcall_scheduled(driver_num, subscribe_num));
+            }
+        }
+        unsafe { fake::Syscalls::yield3([r0, r1, r2]) }
+    }
+
+    unsafe fn syscall1<const CLASS: usize>(registers: [Register; 1]) -> [Register; 2] {
+        unsafe { fake::Syscalls::syscall1::<CLASS>(registers) }
+    }
+
+    unsafe fn syscall2<const CLASS: usize>([r0, r1]: [Register; 2]) -> [Register; 2] {
+        if CLASS == syscall_class::EXIT {
+            exit(r0, r1);
+        }
+        unsafe { fake::Syscalls::syscall2::<CLASS>([r0, r1]) }
+    }
+
+    unsafe fn syscall4<const CLASS: usize>(registers: [Register; 4]) -> [Register; 4] {
+        unsafe { fake::Syscalls::syscall4::<CLASS>(registers) }
+    }
+}
+
+// Applies input events to the fake drivers, one at a time, until ready returns
+// true, waiting for input if necessary. Applying one event at a time gives the
+// process a chance to respond to each event, as if they occurred some time
+// apart. Exits the process if stdin is closed first, as no more upcalls can be
+// scheduled.
+fn wait_until<F: Fn(&fake::Kernel) -> bool>(ready: F) {
+    discard_syscall_log();
+    while !with_host(|host| ready(&host.kernel)) {
+        match with_host(|host| host.events.recv()) {
+            Ok(event) => apply_event(event),
+            Err(_) => {
+                println!("stdin closed while waiting for an upcall, exiting");
+                exit_process(0);
+            }
+        }
+    }
+}
+
+// Applies the next input event, if one has already been received.
+fn apply_next_event() {
+    discard_syscall_log();
+    if let Ok(event) = with_host(|host| host.events.try_recv()) {
+        apply_event(event);
+    }
+}
+
+// The fake kernel logs every system call, which is only useful in unit tests,
+// so we discard the log to avoid accumulating it forever.
+fn discard_syscall_log() {
+    with_host(|host| host.kernel.take_syscall_log());
+}
+
+fn apply_event(event: Event) {
+    match event {
+        Event::Button { index, pressed } => {
+            if with_host(|host| host.buttons.set_pressed(index, pressed)).is_err() {
+                eprintln!("No button {} (there are {})", index, NUM_BUTTONS);
+            }
+        }
+        Event::ConsoleInput(bytes) => with_host(|host| host.console.push_input(&bytes)),
+    }
+}
+
+fn exit(r0: Register, r1: Register) -> ! {
+    let completion_code = u32::try_from(r1).expect("Too large completion code");
+    match u32::try_from(r0) {
+        Ok(exit_id::TERMINATE) => exit_process(completion_code),
+        Ok(exit_id::RESTART) => {
+            println!(
+                "exit-restart called with code {}, which the host does not support",
+                completion_code
+            );
+            exit_process(completion_code);
+        }
+        _ => panic!("Unknown exit number {:?} invoked.", r0),
+    }
+}
+
+// Exits the host process, using the completion code as its exit status.
+fn exit_process(completion_code: u32) -> ! {
+    let _ = std::io::stdout().flush();
+    std::process::exit(completion_code as i32)
+}
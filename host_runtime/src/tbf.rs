@@ -0,0 +1,34 @@
+//! Host versions of `libtock_runtime`'s TBF option macros. Host processes do
+//! not have TBF headers, so the macros only type-check their arguments.
+
+/// Accepted for compatibility with `libtock_runtime::tbf_header_size!`.
+#[macro_export]
+macro_rules! tbf_header_size {
+    {$size:expr} => {
+        const _: usize = $size;
+    }
+}
+
+/// Accepted for compatibility with `libtock_runtime::app_name!`.
+#[macro_export]
+macro_rules! app_name {
+    {$name:expr} => {
+        const _: &str = $name;
+    }
+}
+
+/// Accepted for compatibility with `libtock_runtime::minimum_ram_size!`.
+#[macro_export]
+macro_rules! minimum_ram_size {
+    {$size:expr} => {
+        const _: u32 = $size;
+    }
+}
+
+/// Accepted for compatibility with `libtock_runtime::kernel_version!`.
+#[macro_export]
+macro_rules! kernel_version {
+    {$major:expr, $minor:expr} => {
+        const _: [u32; 2] = [$major, $minor];
+    }
+}
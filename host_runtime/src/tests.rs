@@ -0,0 +1,72 @@
+use crate::input::{parse_line, Event};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake::{self, SyscallDriver};
+
+#[test]
+fn parse_buttons() {
+    let button = |index, pressed| Event::Button { index, pressed };
+    assert_eq!(parse_line(""), Ok(vec![]));
+    assert_eq!(parse_line("  "), Ok(vec![]));
+    assert_eq!(parse_line("press 0"), Ok(vec![button(0, true)]));
+    assert_eq!(parse_line(" release  3 "), Ok(vec![button(3, false)]));
+    assert_eq!(
+        parse_line("click 1"),
+        Ok(vec![button(1, true), button(1, false)])
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert!(parse_line("press").is_err());
+    assert!(parse_line("press one").is_err());
+    assert!(parse_line("press 1 2").is_err());
+    assert!(parse_line("hold 1").is_err());
+}
+
+#[test]
+fn parse_console() {
+    let input = |text: &str| Ok(vec![Event::ConsoleInput(text.as_bytes().to_vec())]);
+    assert_eq!(parse_line("console"), input("\n"));
+    assert_eq!(parse_line("console hello"), input("hello\n"));
+    assert_eq!(parse_line(" console  two  words "), input(" two  words \n"));
+}
+
+#[test]
+fn leds() {
+    let kernel = fake::Kernel::new();
+    let leds = crate::leds::Leds::new();
+    kernel.add_driver(&leds);
+    assert_eq!(leds.id(), 2);
+    assert_eq!(
+        leds.command(0, 0, 0).get_success_u32(),
+        Some(crate::NUM_LEDS as u32)
+    );
+    assert!(leds.command(1, 0, 0).is_success());
+    assert!(leds.command(3, 0, 0).is_success());
+    assert_eq!(
+        leds.command(1, crate::NUM_LEDS as u32, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+thread_local! {
+    static INIT_ORDER: std::cell::RefCell<Vec<u32>> = Default::default();
+}
+fn init_default() {
+    INIT_ORDER.with(|order| order.borrow_mut().push(1000));
+}
+fn init_early() {
+    INIT_ORDER.with(|order| order.borrow_mut().push(100));
+}
+fn init_late() {
+    INIT_ORDER.with(|order| order.borrow_mut().push(2000));
+}
+crate::init_fn! {init_late, priority = 2000}
+crate::init_fn! {init_default}
+crate::init_fn! {init_early, priority = 100}
+
+#[test]
+fn init_fns() {
+    crate::init::run();
+    INIT_ORDER.with(|order| assert_eq!(*order.borrow(), [100, 1000, 2000]));
+}
@@ -0,0 +1,23 @@
+//! Toggles an LED each time a button is pressed, using an async main function.
+
+#![no_main]
+#![no_std]
+
+use libtock2::buttons::Buttons;
+use libtock2::leds::Leds;
+use libtock2::runtime::{set_main, stack_size};
+use libtock_buttons::ButtonState;
+
+set_main! {async main}
+stack_size! {0x200}
+
+async fn main() {
+    if Buttons::enable_interrupts(0).is_err() {
+        return;
+    }
+    while let Ok((_, state)) = Buttons::wait_for_event().await {
+        if state == ButtonState::Pressed {
+            let _ = Leds::toggle(0);
+        }
+    }
+}
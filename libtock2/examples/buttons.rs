@@ -1,27 +1,38 @@
-//! An extremely simple libtock-rs example. Register button events.
+//! An extremely simple libtock-rs example. Register button events, and print
+//! them from the upcall.
 
 #![no_main]
 #![no_std]
 
 use libtock2::buttons::Buttons;
-use libtock2::runtime::{set_main, stack_size};
-use libtock_buttons::ButtonListener;
+use libtock2::println;
+use libtock2::runtime::{set_main, stack_size, TockSyscalls};
+use libtock_buttons::{ButtonListener, ButtonState};
 use libtock_platform::{share, Syscalls};
-use libtock_runtime::TockSyscalls;
 
 set_main! {main}
-stack_size! {0x100}
+stack_size! {0x200}
 
 fn main() {
-    let listener = ButtonListener(|_button, _state| {
-        // TODO when a console driver will be available
-        // println!("button {}: {}", button, state);
+    let listener = ButtonListener(|button, state| {
+        let state = match state {
+            ButtonState::Pressed => "pressed",
+            ButtonState::Released => "released",
+        };
+        println!("button {}: {}", button, state);
     });
-    if let Ok(_buttons_count) = Buttons::count() {
-        if let Ok(()) = share::scope(|subscribe| Buttons::register_listener(&listener, subscribe)) {
-            loop {
-                TockSyscalls::yield_wait();
-            }
+    if let Ok(buttons_count) = Buttons::count() {
+        for button in 0..buttons_count {
+            let _ = Buttons::enable_interrupts(button);
         }
+        // The listener is unregistered when the scope ends, so wait for events
+        // inside it.
+        share::scope(|subscribe| {
+            if Buttons::register_listener(&listener, subscribe).is_ok() {
+                loop {
+                    TockSyscalls::yield_wait();
+                }
+            }
+        });
     }
 }
@@ -0,0 +1,19 @@
+//! An example showing use of the Console API. Prints a greeting, then echoes
+//! console input back to the console.
+
+#![no_main]
+#![no_std]
+
+use libtock2::console::Console;
+use libtock2::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x200}
+
+fn main() {
+    let _ = Console::write(b"Hello, world! Type to echo.\n");
+    let mut buffer = [0; 32];
+    while let Ok(len) = Console::read(&mut buffer) {
+        let _ = Console::write(&buffer[..len]);
+    }
+}
@@ -0,0 +1,28 @@
+//! Prints to the console using deferred formatting, which keeps the format
+//! strings out of the process binary. Run it with the runner, which formats the
+//! lines.
+
+#![no_main]
+#![no_std]
+
+use libtock2::dprintln;
+use libtock2::leds::Leds;
+use libtock2::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x200}
+
+fn main() {
+    dprintln!("Hello from deferred formatting!");
+    match Leds::count() {
+        Ok(count) => dprintln!("This board has {} LEDs", count),
+        Err(error) => dprintln!("Unable to count the LEDs: error {}", error as u32),
+    }
+    dprintln!(
+        "{} in hex is {:#x}, and -{} is {}",
+        1234u32,
+        1234u32,
+        5,
+        -5i32
+    );
+}
@@ -0,0 +1,24 @@
+//! Logs messages to the console using the log crate. Requires the `log`
+//! feature.
+
+#![no_main]
+#![no_std]
+
+use libtock2::log::ConsoleLogger;
+use libtock2::runtime::{init_fn, set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x400}
+
+static LOGGER: ConsoleLogger = ConsoleLogger::new();
+
+fn init_logger() {
+    let _ = libtock2::log::init(&LOGGER, log::LevelFilter::Info);
+}
+init_fn! {init_logger, priority = 200}
+
+fn main() {
+    log::info!("Hello from {}", "libtock_log");
+    log::debug!("This message is filtered out");
+    log::warn!("The answer is {}", 42);
+}
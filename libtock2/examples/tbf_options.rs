@@ -0,0 +1,24 @@
+//! An example showing how to set the process binary's TBF header options.
+//! Prints a greeting, which identifies the process by the name set with
+//! `app_name!`.
+
+#![no_main]
+#![no_std]
+
+use libtock2::println;
+use libtock2::runtime::{
+    app_name, kernel_version, minimum_ram_size, set_main, stack_size, tbf_header_size,
+};
+
+set_main! {main}
+stack_size! {0x200}
+app_name! {"tbf_options"}
+// Leaves room for the TBF headers elf2tab generates from the options below.
+tbf_header_size! {0x60}
+minimum_ram_size! {0x1000}
+// This example only uses system calls that Tock 2.0 supports.
+kernel_version! {2, 0}
+
+fn main() {
+    println!("Hello from tbf_options!");
+}
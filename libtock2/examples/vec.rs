@@ -0,0 +1,26 @@
+//! Builds a Vec on the heap, then prints the heap statistics. Requires the
+//! `alloc` feature.
+
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use libtock2::alloc::TockAllocator;
+use libtock2::low_level_debug::LowLevelDebug;
+use libtock2::runtime::{set_main, stack_size};
+
+set_main! {main}
+stack_size! {0x200}
+
+#[global_allocator]
+static ALLOCATOR: TockAllocator = TockAllocator::with_max_size(0x400);
+
+fn main() {
+    let squares: Vec<u32> = (0..16).map(|i| i * i).collect();
+    LowLevelDebug::print_1(squares.iter().sum());
+
+    let stats = ALLOCATOR.stats();
+    LowLevelDebug::print_2(stats.used as u32, stats.peak as u32);
+}
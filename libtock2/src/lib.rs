@@ -1,21 +1,119 @@
 #![forbid(unsafe_code)]
 #![no_std]
 
+#[cfg(all(not(target_os = "none"), not(feature = "host")))]
+compile_error!("Building libtock2 for the host requires the `host` feature");
+
+#[cfg(all(target_os = "none", feature = "debug_panic"))]
+extern crate libtock_debug_panic;
+#[cfg(all(target_os = "none", not(feature = "debug_panic")))]
 extern crate libtock_small_panic;
 
+#[cfg(feature = "alloc")]
+pub use libtock_alloc as alloc;
+pub use libtock_deferred_fmt as deferred_fmt;
+#[cfg(all(not(target_os = "none"), feature = "host"))]
+pub use libtock_host_runtime as runtime;
 pub use libtock_platform as platform;
+#[cfg(target_os = "none")]
 pub use libtock_runtime as runtime;
+#[doc(hidden)]
+pub use ufmt;
 
 pub mod buttons {
     use libtock_buttons as buttons;
     pub type Buttons = buttons::Buttons<super::runtime::TockSyscalls>;
 }
+pub mod console {
+    use libtock_console as console;
+    pub type Console = console::Console<super::runtime::TockSyscalls>;
+    pub type ConsoleWriter = console::ConsoleWriter<super::runtime::TockSyscalls>;
+    pub type PrintWriter = console::PrintWriter<super::runtime::TockSyscalls>;
+}
 pub mod leds {
     use libtock_leds as leds;
     pub type Leds = leds::Leds<super::runtime::TockSyscalls>;
 }
+#[cfg(feature = "log")]
+pub mod log {
+    use libtock_log as log;
+    pub type ConsoleLogger = log::ConsoleLogger<super::runtime::TockSyscalls>;
+    pub type LowLevelDebugLogger = log::LowLevelDebugLogger<super::runtime::TockSyscalls>;
+    pub use log::{init, record_id};
+}
 pub mod low_level_debug {
     use libtock_low_level_debug as lldb;
     pub type LowLevelDebug = lldb::LowLevelDebug<super::runtime::TockSyscalls>;
     pub use lldb::AlertCode;
 }
+
+/// Prints a line to the console using deferred formatting: the format string is
+/// not stored in the process binary, and the line is formatted by the runner.
+/// Arguments must implement `deferred_fmt::Format`. See `libtock_deferred_fmt`
+/// for details.
+///
+/// # Example
+/// ```ignore
+/// libtock2::dprintln!("Button {} is {}", index, "pressed");
+/// ```
+#[macro_export]
+macro_rules! dprintln {
+    ($($arguments:tt)*) => {{
+        // Text written by upcalls during the print is queued, rather than
+        // splitting the frame.
+        let _ = $crate::deferred_fmt::dwriteln!(
+            $crate::console::Console::print_writer(),
+            $($arguments)*
+        );
+    }};
+}
+
+/// Prints to the console using `ufmt`, blocking until the text is written. The
+/// text is formatted into a small stack buffer, which is written to the console
+/// whenever it fills. Arguments must implement `ufmt::uDisplay` or
+/// `ufmt::uDebug`. Errors are ignored.
+///
+/// `print!` may be used in upcalls: if an upcall prints while another print or
+/// write is waiting for the kernel, the upcall's text is written once the other
+/// print completes. See `libtock_console::Console::print_writer` for details.
+///
+/// # Example
+/// ```ignore
+/// libtock2::print!("Button {} is ", index);
+/// ```
+#[macro_export]
+macro_rules! print {
+    ($($arguments:tt)*) => {{
+        // ufmt's macros refer to `ufmt` by name.
+        use $crate::ufmt;
+        let _ = ufmt::uwrite!($crate::console::Console::print_writer(), $($arguments)*);
+    }};
+}
+
+/// Prints to the console using `ufmt`, with a newline. See `print!`.
+///
+/// # Example
+/// ```ignore
+/// libtock2::println!("Button {} is {}", index, "pressed");
+/// ```
+#[macro_export]
+macro_rules! println {
+    () => {
+        $crate::print!("\n")
+    };
+    ($($arguments:tt)*) => {{
+        // ufmt's macros refer to `ufmt` by name.
+        use $crate::ufmt;
+        let _ = ufmt::uwriteln!($crate::console::Console::print_writer(), $($arguments)*);
+    }};
+}
+
+/// Prints to the console using `ufmt`, with a newline. Tock processes have a
+/// single console, so this is the same as `println!`; it exists so that code
+/// written for `std` needs fewer changes.
+#[macro_export]
+macro_rules! eprintln {
+    ($($arguments:tt)*) => {
+        $crate::println!($($arguments)*)
+    };
+}
@@ -0,0 +1,9 @@
+// Sets the atomic_cas cfg if the target supports pointer-sized atomic
+// compare-and-swap, which log::set_logger requires. This uses the target's
+// cfg values rather than cfg(target_has_atomic), which is not yet stable.
+fn main() {
+    let has_atomic = std::env::var("CARGO_CFG_TARGET_HAS_ATOMIC").unwrap_or_default();
+    if has_atomic.split(',').any(|width| width == "ptr") {
+        println!("cargo:rustc-cfg=atomic_cas");
+    }
+}
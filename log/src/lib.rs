@@ -0,0 +1,173 @@
+//! `libtock_log` implements the [`log`](https://docs.rs/log) crate's logging
+//! facade for Tock processes. It provides two loggers:
+//! - `ConsoleLogger` prints each record, along with its level and target, to
+//!   the console. If the console is not available, or the record is logged
+//!   from an upcall that interrupted a console write, it reports the record
+//!   the same way `LowLevelDebugLogger` does.
+//! - `LowLevelDebugLogger` only reports each record's level and a numeric
+//!   record ID (see `record_id`) using LowLevelDebug. It does not format log
+//!   messages, so it is much smaller than `ConsoleLogger`.
+//!
+//! The loggers do not allocate. A process binary registers one with `init`,
+//! typically from an initialization function:
+//! ```ignore
+//! static LOGGER: libtock_log::ConsoleLogger<TockSyscalls> = libtock_log::ConsoleLogger::new();
+//!
+//! fn init_logger() {
+//!     let _ = libtock_log::init(&LOGGER, log::LevelFilter::Info);
+//! }
+//! libtock_runtime::init_fn! {init_logger, priority = 200}
+//! ```
+//! Records are filtered at runtime by the maximum level passed to `init` (which
+//! can be changed with `log::set_max_level`), and at compile time by the
+//! `max_level_*` and `release_max_level_*` features.
+//!
+//! The `log` crate stores the logger as a trait object, so `libtock_log` cannot
+//! be used by position-independent process binaries.
+
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_console::{Console, ConsoleWriter};
+use libtock_low_level_debug::LowLevelDebug;
+use libtock_platform::{ErrorCode, Syscalls};
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+/// Registers `logger` as the global logger, and sets the maximum level of the
+/// records that are logged.
+pub fn init(logger: &'static dyn Log, level: LevelFilter) -> Result<(), SetLoggerError> {
+    #[cfg(atomic_cas)]
+    log::set_logger(logger)?;
+    // Some Tock targets (e.g. ARMv6-M and RV32IMC) lack the atomic
+    // compare-and-swap set_logger needs.
+    // Safety: Tock processes are single-threaded, so set_logger_racy cannot
+    // race with another call to set_logger_racy or with logging.
+    #[cfg(all(not(atomic_cas), target_os = "none"))]
+    unsafe { log::set_logger_racy(logger) }?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// Returns the numeric ID `LowLevelDebugLogger` reports for a record logged at
+/// `file:line`, which is the 32-bit FNV-1a hash of the file name followed by
+/// the line number (as little-endian bytes). IDs can be mapped back to log
+/// statements by computing the IDs of the process binary's log statements.
+pub const fn record_id(file: &str, line: u32) -> u32 {
+    const FNV_OFFSET_BASIS: u32 = 0x811c9dc5;
+    const FNV_PRIME: u32 = 0x01000193;
+    let file = file.as_bytes();
+    let line = line.to_le_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+    while i < file.len() + line.len() {
+        let byte = if i < file.len() {
+            file[i]
+        } else {
+            line[i - file.len()]
+        };
+        hash = (hash ^ byte as u32).wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// A logger that prints records to the console, in the format
+/// `[LEVEL target] message`.
+pub struct ConsoleLogger<S> {
+    // Log requires loggers to be Send and Sync, which fn() -> S is regardless
+    // of S.
+    _syscalls: PhantomData<fn() -> S>,
+}
+
+impl<S> ConsoleLogger<S> {
+    pub const fn new() -> Self {
+        Self::NEW
+    }
+
+    // Function pointer types (such as _syscalls') cannot yet be used in const
+    // fns, but can be used in associated consts.
+    const NEW: Self = Self {
+        _syscalls: PhantomData,
+    };
+}
+
+impl<S> Default for ConsoleLogger<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Syscalls> Log for ConsoleLogger<S> {
+    // Records above the maximum level are filtered by the log macros.
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        // A record logged from an upcall that interrupted a console write would
+        // be queued until that write completes, and truncated if it does not
+        // fit in the queue, so it is reported instead.
+        if Console::<S>::writing() || print_record(&mut Console::<S>::writer(), record).is_err() {
+            report_record::<S>(record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// A logger that reports records using LowLevelDebug. For each record it
+/// prints two numbers: the record's level (1 for `Error` through 5 for
+/// `Trace`), and the record's ID as computed by `record_id`.
+pub struct LowLevelDebugLogger<S> {
+    _syscalls: PhantomData<fn() -> S>,
+}
+
+impl<S> LowLevelDebugLogger<S> {
+    pub const fn new() -> Self {
+        Self::NEW
+    }
+
+    // Function pointer types (such as _syscalls') cannot yet be used in const
+    // fns, but can be used in associated consts.
+    const NEW: Self = Self {
+        _syscalls: PhantomData,
+    };
+}
+
+impl<S> Default for LowLevelDebugLogger<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Syscalls> Log for LowLevelDebugLogger<S> {
+    // Records above the maximum level are filtered by the log macros.
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        report_record::<S>(record);
+    }
+
+    fn flush(&self) {}
+}
+
+fn print_record<S: Syscalls>(
+    writer: &mut ConsoleWriter<S>,
+    record: &Record,
+) -> Result<(), ErrorCode> {
+    use ufmt::uwrite;
+    uwrite!(writer, "[{} {}] ", record.level().as_str(), record.target())?;
+    // The message is core::fmt::Arguments, which ufmt cannot print.
+    core::fmt::write(writer, *record.args()).map_err(|_| ErrorCode::Fail)?;
+    uwrite!(writer, "\n")
+}
+
+fn report_record<S: Syscalls>(record: &Record) {
+    let id = record_id(record.file().unwrap_or(""), record.line().unwrap_or(0));
+    LowLevelDebug::<S>::print_2(record.level() as u32, id);
+}
+
+#[cfg(test)]
+mod tests;
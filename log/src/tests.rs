@@ -0,0 +1,114 @@
+use super::{record_id, ConsoleLogger, LowLevelDebugLogger};
+use libtock_console::Console;
+use libtock_unittest::fake;
+use log::{Level, LevelFilter, Log, Record};
+
+fn record(level: Level, args: core::fmt::Arguments) -> Record {
+    Record::builder()
+        .level(level)
+        .target("app")
+        .file(Some("src/main.rs"))
+        .line(Some(12))
+        .args(args)
+        .build()
+}
+
+#[test]
+fn record_ids() {
+    // Reference values computed with a separate FNV-1a implementation.
+    assert_eq!(record_id("", 0), 0x4b95f515);
+    assert_eq!(record_id("a", 1), 0x46accefd);
+    assert_ne!(record_id("src/main.rs", 12), record_id("src/main.rs", 13));
+    assert_ne!(record_id("src/main.rs", 12), record_id("src/lib.rs", 12));
+}
+
+#[test]
+fn console_logger() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    let logger = ConsoleLogger::<fake::Syscalls>::new();
+    logger.log(&record(Level::Warn, format_args!("{} is {:x}", "x", 255)));
+    assert_eq!(console.take_bytes(), b"[WARN app] x is ff\n");
+}
+
+// Verifies ConsoleLogger falls back to LowLevelDebug if the console is
+// missing.
+#[test]
+fn console_logger_fallback() {
+    let kernel = fake::Kernel::new();
+    let low_level_debug = fake::LowLevelDebug::new();
+    kernel.add_driver(&low_level_debug);
+    let logger = ConsoleLogger::<fake::Syscalls>::new();
+    logger.log(&record(Level::Error, format_args!("failed")));
+    assert_eq!(
+        low_level_debug.take_messages(),
+        [fake::Message::Print2(1, record_id("src/main.rs", 12))]
+    );
+}
+
+// Verifies ConsoleLogger reports a record logged from an upcall that
+// interrupts a console write using LowLevelDebug, rather than writing to the
+// console.
+#[test]
+fn console_logger_in_upcall() {
+    use libtock_platform::subscribe::FnUpcall;
+    use libtock_platform::{share, DefaultConfig, Subscribe, Syscalls};
+    // The console's read upcall.
+    const DRIVER_NUM: u32 = 1;
+    const READ: u32 = 2;
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    let low_level_debug = fake::LowLevelDebug::new();
+    kernel.add_driver(&low_level_debug);
+    let logger = ConsoleLogger::<fake::Syscalls>::new();
+    let upcall = FnUpcall(|_, _, _| logger.log(&record(Level::Info, format_args!("upcall"))));
+    share::scope::<Subscribe<_, DRIVER_NUM, READ>, _, _>(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, READ>(subscribe, &upcall)
+            .unwrap();
+        libtock_unittest::upcall::schedule(DRIVER_NUM, READ, (0, 0, 0)).unwrap();
+        assert_eq!(Console::<fake::Syscalls>::write(b"outer"), Ok(()));
+    });
+    assert_eq!(console.take_bytes(), b"outer");
+    assert_eq!(
+        low_level_debug.take_messages(),
+        [fake::Message::Print2(3, record_id("src/main.rs", 12))]
+    );
+}
+
+#[test]
+fn low_level_debug_logger() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    let low_level_debug = fake::LowLevelDebug::new();
+    kernel.add_driver(&low_level_debug);
+    let logger = LowLevelDebugLogger::<fake::Syscalls>::new();
+    logger.log(&record(Level::Trace, format_args!("ignored")));
+    assert_eq!(
+        low_level_debug.take_messages(),
+        [fake::Message::Print2(5, record_id("src/main.rs", 12))]
+    );
+    assert_eq!(console.take_bytes(), b"");
+}
+
+// This is the only test that uses the global logger, as it can only be set
+// once per process.
+#[test]
+fn init() {
+    static LOGGER: ConsoleLogger<fake::Syscalls> = ConsoleLogger::new();
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    assert!(super::init(&LOGGER, LevelFilter::Info).is_ok());
+    log::info!(target: "app", "hello");
+    log::debug!(target: "app", "filtered");
+    assert_eq!(console.take_bytes(), b"[INFO app] hello\n");
+
+    log::set_max_level(LevelFilter::Debug);
+    log::debug!(target: "app", "not filtered");
+    assert_eq!(console.take_bytes(), b"[DEBUG app] not filtered\n");
+
+    assert!(super::init(&LOGGER, LevelFilter::Info).is_err());
+}
@@ -0,0 +1,65 @@
+use super::tbf::package_name;
+use super::PANIC_EXIT_CODE;
+use core::cell::Cell;
+use core::panic::PanicInfo;
+use libtock_console::{Console, ConsoleWriter};
+use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+use libtock_platform::{ErrorCode, SingleThreaded, Syscalls};
+use libtock_runtime::TockSyscalls;
+
+#[panic_handler]
+fn panic_handler(info: &PanicInfo) -> ! {
+    // Printing the panic yields (which may run upcalls) and formats
+    // user-provided values, either of which may panic again. In that case, only
+    // signal the panic using LowLevelDebug. Likewise, text printed while a
+    // console write is in progress (i.e. from an upcall that interrupted the
+    // write) is queued until that write completes, which it never will.
+    if !PANICKING.replace(true)
+        && !Console::<TockSyscalls>::writing()
+        && Console::<TockSyscalls>::exists()
+        && print_panic(&mut Console::writer(), info).is_ok()
+    {
+        TockSyscalls::exit_terminate(PANIC_EXIT_CODE);
+    }
+
+    LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    TockSyscalls::exit_terminate(PANIC_EXIT_CODE);
+}
+
+fn print_panic(
+    writer: &mut ConsoleWriter<TockSyscalls>,
+    info: &PanicInfo,
+) -> Result<(), ErrorCode> {
+    use ufmt::uwrite;
+    uwrite!(writer, "{} panicked", process_name().unwrap_or("Process"))?;
+    // The message is core::fmt::Arguments, which ufmt cannot print.
+    if let Some(message) = info.message() {
+        uwrite!(writer, " at '")?;
+        core::fmt::write(writer, *message).map_err(|_| ErrorCode::Fail)?;
+        uwrite!(writer, "'")?;
+    }
+    if let Some(location) = info.location() {
+        uwrite!(
+            writer,
+            ", {}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        )?;
+    }
+    uwrite!(writer, "\n")
+}
+
+// Set when the panic handler is first entered.
+static PANICKING: SingleThreaded<Cell<bool>> = SingleThreaded::new(Cell::new(false));
+
+// Returns the process' name, which is stored in the Package Name TLV of its TBF
+// header.
+fn process_name() -> Option<&'static str> {
+    let start = TockSyscalls::memop_flash_start().ok()?;
+    let end = TockSyscalls::memop_flash_end().ok()?;
+    // Safety: The process' flash region (which starts with its TBF header) is
+    // readable by the process and is not modified while the process runs.
+    let flash = unsafe { core::slice::from_raw_parts(start, (end as usize) - (start as usize)) };
+    package_name(flash)
+}
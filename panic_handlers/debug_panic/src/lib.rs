@@ -0,0 +1,26 @@
+//! A panic handler that prints the panic's message and location, along with
+//! the process' name, to the console. For example:
+//! ```text
+//! blink panicked at 'index out of bounds: the len is 3 but the index is 4', src/main.rs:12:5
+//! ```
+//! If the console is not available, or the panic happened in an upcall that
+//! interrupted a console write, it signals the panic using LowLevelDebug
+//! instead. The process then terminates with completion code
+//! `PANIC_EXIT_CODE`.
+
+#![feature(panic_info_message)]
+#![no_std]
+
+// The panic handler makes system calls, so it is only built for Tock. This lets
+// the TBF header parsing be unit tested on the host.
+#[cfg(target_os = "none")]
+mod handler;
+#[cfg(any(target_os = "none", test))]
+mod tbf;
+#[cfg(test)]
+mod tbf_tests;
+
+/// The completion code a process exits with when it panics. It is distinct
+/// from the completion codes `Termination` uses for errors, which are
+/// `ErrorCode` values.
+pub const PANIC_EXIT_CODE: u32 = 0x401;
@@ -0,0 +1,30 @@
+// Returns the package name stored in the Package Name TLV of `header`, a TBF
+// header. Returns None if the header is malformed or has no Package Name TLV.
+pub fn package_name(header: &[u8]) -> Option<&str> {
+    let read_u16 = |bytes: &[u8], offset: usize| -> Option<usize> {
+        let bytes = bytes.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+    };
+    if read_u16(header, 0)? != TBF_VERSION {
+        return None;
+    }
+    let header_size = read_u16(header, 2)?;
+    let mut tlvs = header.get(TBF_BASE_HEADER_SIZE..header_size)?;
+    while !tlvs.is_empty() {
+        let tlv_type = read_u16(tlvs, 0)?;
+        let len = read_u16(tlvs, 2)?;
+        if tlv_type == TBF_PACKAGE_NAME {
+            return core::str::from_utf8(tlvs.get(4..4 + len)?).ok();
+        }
+        // TLV values are padded to a multiple of 4 bytes.
+        tlvs = tlvs.get(4 + (len + 3) / 4 * 4..)?;
+    }
+    None
+}
+
+// The TBF header version elf2tab generates.
+const TBF_VERSION: usize = 2;
+// The size of the TBF base header, which is followed by the TLVs.
+const TBF_BASE_HEADER_SIZE: usize = 16;
+// The type of the Package Name TLV.
+const TBF_PACKAGE_NAME: usize = 3;
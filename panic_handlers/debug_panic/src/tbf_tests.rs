@@ -0,0 +1,73 @@
+extern crate std;
+
+use super::tbf::package_name;
+use std::vec::Vec;
+
+// Builds a TBF header containing the given (type, value) TLVs.
+fn header(tlvs: &[(u16, &[u8])]) -> Vec<u8> {
+    let mut header = std::vec![0; 16];
+    for &(tlv_type, value) in tlvs {
+        header.extend_from_slice(&tlv_type.to_le_bytes());
+        header.extend_from_slice(&(value.len() as u16).to_le_bytes());
+        header.extend_from_slice(value);
+        header.resize((header.len() + 3) / 4 * 4, 0);
+    }
+    let header_size = header.len() as u16;
+    header[0..2].copy_from_slice(&2u16.to_le_bytes());
+    header[2..4].copy_from_slice(&header_size.to_le_bytes());
+    header
+}
+
+#[test]
+fn valid() {
+    assert_eq!(package_name(&header(&[(3, b"blink")])), Some("blink"));
+    // The header is followed by the rest of the process binary.
+    let mut flash = header(&[(3, b"leds")]);
+    flash.extend_from_slice(&[0xff; 8]);
+    assert_eq!(package_name(&flash), Some("leds"));
+    assert_eq!(package_name(&header(&[(3, b"")])), Some(""));
+}
+
+#[test]
+fn after_padding() {
+    // The first TLV's 5-byte value is padded to 8 bytes.
+    assert_eq!(
+        package_name(&header(&[(5, b"\x01\x02\x03\x04\x05"), (3, b"app")])),
+        Some("app")
+    );
+}
+
+#[test]
+fn no_package_name() {
+    assert_eq!(package_name(&header(&[])), None);
+    assert_eq!(package_name(&header(&[(5, b"\x01\x02\x03")])), None);
+}
+
+#[test]
+fn truncated() {
+    assert_eq!(package_name(&[]), None);
+    assert_eq!(package_name(&[2, 0]), None);
+    // The header is shorter than its header size.
+    let flash = header(&[(3, b"blink")]);
+    assert_eq!(package_name(&flash[..flash.len() - 4]), None);
+    // The TLV is longer than the header.
+    let mut flash = header(&[(3, b"blink")]);
+    flash[18] = 9;
+    assert_eq!(package_name(&flash), None);
+    // The header size cuts the TLV's type and length short.
+    let mut flash = header(&[(3, b"blink")]);
+    flash[2] = 18;
+    assert_eq!(package_name(&flash), None);
+}
+
+#[test]
+fn wrong_version() {
+    let mut flash = header(&[(3, b"blink")]);
+    flash[0] = 1;
+    assert_eq!(package_name(&flash), None);
+}
+
+#[test]
+fn non_utf8_name() {
+    assert_eq!(package_name(&header(&[(3, b"bl\xffnk")])), None);
+}
@@ -1,4 +1,4 @@
-use crate::share::List;
+use crate::share::{List, StaticList};
 use crate::Syscalls;
 use core::marker::PhantomData;
 
@@ -58,6 +58,12 @@ impl<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> List
 {
 }
 
+// Safety: An `AllowRo<'static, ...>` only shares `&'static [u8]` buffers.
+unsafe impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const BUFFER_NUM: u32> StaticList
+    for AllowRo<'static, S, DRIVER_NUM, BUFFER_NUM>
+{
+}
+
 // -----------------------------------------------------------------------------
 // `Config` trait
 // -----------------------------------------------------------------------------
@@ -1,5 +1,5 @@
-use crate::share::List;
-use crate::Syscalls;
+use crate::share::{Handle, List, StaticList};
+use crate::{ErrorCode, Syscalls};
 use core::marker::PhantomData;
 
 // -----------------------------------------------------------------------------
@@ -58,6 +58,76 @@ impl<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> List
 {
 }
 
+// Safety: An `AllowRw<'static, ...>` only shares `&'static mut [u8]` buffers.
+unsafe impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const BUFFER_NUM: u32> StaticList
+    for AllowRw<'static, S, DRIVER_NUM, BUFFER_NUM>
+{
+}
+
+// -----------------------------------------------------------------------------
+// `StaticAllowRw` struct
+// -----------------------------------------------------------------------------
+
+/// Shares a `'static` buffer with the kernel using Read-Write Allow, outside of
+/// `share::scope`. The buffer remains shared until `revoke` is called, which
+/// revokes the kernel's access and returns the buffer. If a `StaticAllowRw` is
+/// dropped, the buffer remains shared until another buffer is allowed with the
+/// same driver and buffer numbers, and cannot be reclaimed.
+///
+/// # Example
+/// ```ignore
+/// static mut BUFFER: [u8; 16] = [0; 16];
+/// // Safety: BUFFER is not accessed anywhere else.
+/// let buffer = unsafe { &mut BUFFER };
+/// let allow = StaticAllowRw::<TockSyscalls, 1, 0>::new::<DefaultConfig>(buffer)
+///     .map_err(|(_buffer, error)| error)?;
+/// // ... wait for the kernel to write into the buffer ...
+/// let buffer = allow.revoke();
+/// ```
+pub struct StaticAllowRw<S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> {
+    // The buffer is stored as a raw pointer rather than a `&'static mut [u8]`
+    // because the kernel may write to it while it is shared.
+    buffer: *mut [u8],
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const BUFFER_NUM: u32>
+    StaticAllowRw<S, DRIVER_NUM, BUFFER_NUM>
+{
+    /// Shares `buffer` with the kernel. If the Read-Write Allow call fails,
+    /// returns the buffer along with the error.
+    pub fn new<CONFIG: Config>(
+        buffer: &'static mut [u8],
+    ) -> Result<Self, (&'static mut [u8], ErrorCode)> {
+        let buffer: *mut [u8] = buffer;
+        // Safety: buffer came from a &'static mut [u8], so it is valid for the
+        // rest of the process' execution and no other references to it exist.
+        let result = S::allow_rw::<CONFIG, DRIVER_NUM, BUFFER_NUM>(Handle::new_static(), unsafe {
+            &mut *buffer
+        });
+        match result {
+            Ok(()) => Ok(Self {
+                buffer,
+                _syscalls: PhantomData,
+            }),
+            // Safety: The Allow call failed, so the kernel does not have access
+            // to the buffer, and the reference passed to allow_rw no longer
+            // exists.
+            Err(error) => Err((unsafe { &mut *buffer }, error)),
+        }
+    }
+
+    /// Revokes the kernel's access to the buffer, and returns the buffer.
+    pub fn revoke(self) -> &'static mut [u8] {
+        S::unallow_rw(DRIVER_NUM, BUFFER_NUM);
+        // Safety: The buffer was only shared with this driver and buffer
+        // number, and the kernel's access to it has been revoked (either by
+        // unallow_rw or by a later Allow call with the same numbers). `self` is
+        // consumed, so this is the only reference to the buffer.
+        unsafe { &mut *self.buffer }
+    }
+}
+
 // -----------------------------------------------------------------------------
 // `Config` trait
 // -----------------------------------------------------------------------------
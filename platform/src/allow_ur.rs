@@ -0,0 +1,126 @@
+use crate::share::{Handle, List, StaticList};
+use crate::{ErrorCode, Syscalls};
+use core::cell::Cell;
+use core::marker::PhantomData;
+
+// -----------------------------------------------------------------------------
+// `AllowUr` struct
+// -----------------------------------------------------------------------------
+
+/// A `share::Handle<AllowUr>` instance allows safe code to call Tock's
+/// Userspace-Readable Allow system call, by guaranteeing the buffer will be
+/// revoked before 'share ends. It is intended for use with the `share::scope`
+/// function, which offers a safe interface for constructing
+/// `share::Handle<AllowUr>` instances.
+///
+/// Unlike a Read-Write Allow buffer, a Userspace-Readable Allow buffer may be
+/// read by the process while it is shared with the kernel. Because the kernel
+/// may modify the buffer, it is shared as a `&[Cell<u8>]` rather than a
+/// `&mut [u8]`. The kernel only modifies the buffer while the process is not
+/// running. Changes made by the kernel are visible to the process after its
+/// next Yield, Subscribe, Command, or Allow call (Memop calls do not act as a
+/// barrier for the buffer's contents).
+pub struct AllowUr<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> {
+    _syscalls: PhantomData<S>,
+
+    // Make this struct invariant with respect to the 'share lifetime.
+    //
+    // If AllowUr were covariant with respect to 'share, then an
+    // `AllowUr<'static, ...>` could be used to share a buffer that has a
+    // shorter lifetime. The capsule would still have access to the memory after
+    // the buffer is deallocated and the memory re-used (e.g. if the buffer is
+    // on the stack), allowing it to cause undefined behavior in the process.
+    // Therefore, AllowUr cannot be covariant with respect to 'share.
+    // Contravariance would not have this issue, but would still be confusing
+    // and would be unexpected.
+    //
+    // Additionally, this makes AllowUr !Sync, which is probably desirable, as
+    // Sync would allow for races between threads sharing buffers with the
+    // kernel.
+    _share: PhantomData<Cell<&'share [Cell<u8>]>>,
+}
+
+// We can't derive(Default) because S is not Default, and derive(Default)
+// generates a Default implementation that requires S to be Default. Instead, we
+// manually implement Default.
+impl<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> Default
+    for AllowUr<'share, S, DRIVER_NUM, BUFFER_NUM>
+{
+    fn default() -> Self {
+        Self {
+            _syscalls: PhantomData,
+            _share: PhantomData,
+        }
+    }
+}
+
+impl<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> Drop
+    for AllowUr<'share, S, DRIVER_NUM, BUFFER_NUM>
+{
+    fn drop(&mut self) {
+        S::unallow_ur(DRIVER_NUM, BUFFER_NUM);
+    }
+}
+
+impl<'share, S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> List
+    for AllowUr<'share, S, DRIVER_NUM, BUFFER_NUM>
+{
+}
+
+// Safety: An `AllowUr<'static, ...>` only shares `&'static [Cell<u8>]` buffers.
+unsafe impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const BUFFER_NUM: u32> StaticList
+    for AllowUr<'static, S, DRIVER_NUM, BUFFER_NUM>
+{
+}
+
+// -----------------------------------------------------------------------------
+// `StaticAllowUr` struct
+// -----------------------------------------------------------------------------
+
+/// Shares a `'static` buffer with the kernel using Userspace-Readable Allow,
+/// outside of `share::scope`. The buffer remains shared until `revoke` is
+/// called. If a `StaticAllowUr` is dropped, the buffer remains shared until
+/// another buffer is allowed with the same driver and buffer numbers.
+pub struct StaticAllowUr<S: Syscalls, const DRIVER_NUM: u32, const BUFFER_NUM: u32> {
+    buffer: &'static [Cell<u8>],
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const BUFFER_NUM: u32>
+    StaticAllowUr<S, DRIVER_NUM, BUFFER_NUM>
+{
+    /// Shares `buffer` with the kernel.
+    pub fn new<CONFIG: Config>(buffer: &'static [Cell<u8>]) -> Result<Self, ErrorCode> {
+        S::allow_ur::<CONFIG, DRIVER_NUM, BUFFER_NUM>(Handle::new_static(), buffer)?;
+        Ok(Self {
+            buffer,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// Returns the shared buffer, which the process may read while it is
+    /// shared.
+    pub fn buffer(&self) -> &'static [Cell<u8>] {
+        self.buffer
+    }
+
+    /// Revokes the kernel's access to the buffer, and returns the buffer.
+    pub fn revoke(self) -> &'static [Cell<u8>] {
+        S::unallow_ur(DRIVER_NUM, BUFFER_NUM);
+        self.buffer
+    }
+}
+
+// -----------------------------------------------------------------------------
+// `Config` trait
+// -----------------------------------------------------------------------------
+
+/// `Config` configures the behavior of the Userspace-Readable Allow system
+/// call. It should generally be passed through by drivers, to allow application
+/// code to configure error handling.
+pub trait Config {
+    /// Called if a Userspace-Readable Allow call succeeds and returns a
+    /// non-zero buffer. In some applications, this may indicate unexpected
+    /// reentrance. By default, the non-zero buffer is ignored.
+    fn returned_nonzero_buffer(_driver_num: u32, _buffer_num: u32) {}
+}
@@ -5,6 +5,18 @@ pub mod exit_id {
     pub const RESTART: u32 = 1;
 }
 
+pub mod memop_id {
+    pub const BRK: u32 = 0;
+    pub const SBRK: u32 = 1;
+    pub const PROCESS_RAM_START: u32 = 2;
+    pub const PROCESS_RAM_END: u32 = 3;
+    pub const FLASH_START: u32 = 4;
+    pub const FLASH_END: u32 = 5;
+    pub const GRANT_REGION_START: u32 = 6;
+    pub const DEBUG_STACK_START: u32 = 10;
+    pub const DEBUG_HEAP_START: u32 = 11;
+}
+
 pub mod syscall_class {
     pub const SUBSCRIBE: usize = 1;
     pub const COMMAND: usize = 2;
@@ -12,9 +24,11 @@ pub mod syscall_class {
     pub const ALLOW_RO: usize = 4;
     pub const MEMOP: usize = 5;
     pub const EXIT: usize = 6;
+    pub const ALLOW_UR: usize = 7;
 }
 
 pub mod yield_id {
     pub const NO_WAIT: u32 = 0;
     pub const WAIT: u32 = 1;
+    pub const WAIT_FOR: u32 = 2;
 }
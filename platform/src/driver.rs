@@ -0,0 +1,346 @@
+//! `driver!` generates the boilerplate of a driver API crate from a compact
+//! description of the driver's system call interface.
+
+/// Defines a driver API type. `driver!` generates the `DRIVER_NUM` constant,
+/// a constant for each command, subscribe, and allow number, a
+/// `struct Name<S: Syscalls>(S)`, and a method for each command, subscribe, and
+/// allow. Each section must be present, but may be empty.
+///
+/// Command methods take up to two arguments, each of which must implement
+/// `Into<u32>`, and return `Result<T, ErrorCode>`, where `T` is the return type
+/// given in the description (or `()` if no return type is given). `T` must
+/// implement `command_return::SuccessData`.
+///
+/// If the optional `fake` line is present, `driver!` also generates a skeleton
+/// `libtock_unittest::fake::SyscallDriver` with the given name, for use in the
+/// API crate's unit tests. The skeleton fake is only compiled in `cfg(test)`,
+/// and requires `libtock_unittest` as a dev-dependency.
+///
+/// # Example
+/// ```ignore
+/// libtock_platform::driver! {
+///     /// The LEDs driver
+///     pub struct Leds;
+///
+///     const DRIVER_NUM: u32 = 2;
+///
+///     commands {
+///         /// Returns the number of LEDs.
+///         LEDS_COUNT = 0 => pub fn count() -> u32;
+///         LED_ON = 1 => pub fn on(led: u32);
+///     }
+///
+///     subscribes {
+///         EVENT = 0 => pub fn register_listener;
+///     }
+///
+///     allow_ro {
+///         PATTERN = 0 => pub fn allow_pattern;
+///     }
+///
+///     allow_rw {}
+///
+///     fake FakeLeds;
+/// }
+/// ```
+#[macro_export]
+macro_rules! driver {
+    // Generates the driver's constants, struct, and methods.
+    (@api
+        ($($attr:tt)*) $vis:vis $name:ident ($driver_num:expr)
+        {$(
+            $(#[$cmd_attr:meta])*
+            $cmd_const:ident = $cmd_num:expr =>
+                $cmd_vis:vis fn $cmd_name:ident($($arg:ident: $arg_ty:ty),*) $(-> $ret:ty)?;
+        )*}
+        {$(
+            $(#[$sub_attr:meta])*
+            $sub_const:ident = $sub_num:expr => $sub_vis:vis fn $sub_name:ident;
+        )*}
+        {$(
+            $(#[$ro_attr:meta])*
+            $ro_const:ident = $ro_num:expr => $ro_vis:vis fn $ro_name:ident;
+        )*}
+        {$(
+            $(#[$rw_attr:meta])*
+            $rw_const:ident = $rw_num:expr => $rw_vis:vis fn $rw_name:ident;
+        )*}
+    ) => {
+        $($attr)*
+        $vis struct $name<S: $crate::Syscalls>(S);
+
+        impl<S: $crate::Syscalls> $name<S> {
+            $(
+                $(#[$cmd_attr])*
+                $cmd_vis fn $cmd_name($($arg: $arg_ty),*)
+                    -> Result<$crate::__driver_return_type!($($ret)?), $crate::ErrorCode>
+                {
+                    let (argument0, argument1): (u32, u32) =
+                        $crate::__driver_command_args!($($arg),*);
+                    S::command(DRIVER_NUM, $cmd_const, argument0, argument1).to_result()
+                }
+            )*
+
+            $(
+                $(#[$sub_attr])*
+                $sub_vis fn $sub_name<
+                    'share,
+                    IDS: $crate::subscribe::SupportsId<DRIVER_NUM, $sub_const>,
+                    U: $crate::Upcall<IDS>,
+                >(
+                    upcall: &'share U,
+                    subscribe: $crate::share::Handle<
+                        $crate::Subscribe<'share, S, DRIVER_NUM, $sub_const>,
+                    >,
+                ) -> Result<(), $crate::ErrorCode> {
+                    S::subscribe::<_, _, $crate::DefaultConfig, DRIVER_NUM, $sub_const>(
+                        subscribe, upcall,
+                    )
+                }
+            )*
+
+            $(
+                $(#[$ro_attr])*
+                $ro_vis fn $ro_name<'share>(
+                    allow_ro: $crate::share::Handle<
+                        $crate::AllowRo<'share, S, DRIVER_NUM, $ro_const>,
+                    >,
+                    buffer: &'share [u8],
+                ) -> Result<(), $crate::ErrorCode> {
+                    S::allow_ro::<$crate::DefaultConfig, DRIVER_NUM, $ro_const>(allow_ro, buffer)
+                }
+            )*
+
+            $(
+                $(#[$rw_attr])*
+                $rw_vis fn $rw_name<'share>(
+                    allow_rw: $crate::share::Handle<
+                        $crate::AllowRw<'share, S, DRIVER_NUM, $rw_const>,
+                    >,
+                    buffer: &'share mut [u8],
+                ) -> Result<(), $crate::ErrorCode> {
+                    S::allow_rw::<$crate::DefaultConfig, DRIVER_NUM, $rw_const>(allow_rw, buffer)
+                }
+            )*
+        }
+
+        const DRIVER_NUM: u32 = $driver_num;
+
+        // Command IDs
+        $(const $cmd_const: u32 = $cmd_num;)*
+
+        // Subscribe IDs
+        $(const $sub_const: u32 = $sub_num;)*
+
+        // Read-Only Allow buffer IDs
+        $(const $ro_const: u32 = $ro_num;)*
+
+        // Read-Write Allow buffer IDs
+        $(const $rw_const: u32 = $rw_num;)*
+    };
+
+    // Generates the skeleton fake driver.
+    (@fake $fake:ident
+        {$($commands:tt)*}
+        {$(
+            $(#[$sub_attr:meta])*
+            $sub_const:ident = $sub_num:expr => $sub_vis:vis fn $sub_name:ident;
+        )*}
+        {$(
+            $(#[$ro_attr:meta])*
+            $ro_const:ident = $ro_num:expr => $ro_vis:vis fn $ro_name:ident;
+        )*}
+        {$(
+            $(#[$rw_attr:meta])*
+            $rw_const:ident = $rw_num:expr => $rw_vis:vis fn $rw_name:ident;
+        )*}
+    ) => {
+        #[cfg(test)]
+        pub use __driver_fake::$fake;
+
+        #[cfg(test)]
+        mod __driver_fake {
+            // The API crate may be no_std.
+            extern crate std;
+
+            use core::cell::RefCell;
+            use libtock_unittest::{command_return, RoAllowBuffer, RwAllowBuffer};
+            use std::collections::HashMap;
+            use std::rc::Rc;
+            use std::vec::Vec;
+            use $crate::{CommandReturn, ErrorCode};
+
+            /// A skeleton fake driver generated by `driver!`. It logs Command
+            /// calls and returns the values configured with
+            /// `set_command_return`, and stores the buffers shared with it.
+            #[derive(Default)]
+            pub struct $fake {
+                command_returns: RefCell<HashMap<u32, CommandReturn>>,
+                command_log: RefCell<Vec<(u32, u32, u32)>>,
+                ro_buffers: RefCell<HashMap<u32, RoAllowBuffer>>,
+                rw_buffers: RefCell<HashMap<u32, RwAllowBuffer>>,
+            }
+
+            impl $fake {
+                pub fn new() -> Rc<$fake> {
+                    Default::default()
+                }
+
+                /// Sets the value returned by the given command. By default,
+                /// command 0 returns success and all other commands fail with
+                /// `NoSupport`.
+                pub fn set_command_return(&self, command_id: u32, command_return: CommandReturn) {
+                    self.command_returns
+                        .borrow_mut()
+                        .insert(command_id, command_return);
+                }
+
+                /// Returns the (command ID, argument 0, argument 1) of each
+                /// Command call made since the last `take_command_log` call.
+                pub fn take_command_log(&self) -> Vec<(u32, u32, u32)> {
+                    self.command_log.take()
+                }
+
+                /// Returns a copy of the given Read-Only Allow buffer's
+                /// contents.
+                pub fn ro_buffer(&self, buffer_num: u32) -> Option<Vec<u8>> {
+                    self.ro_buffers
+                        .borrow()
+                        .get(&buffer_num)
+                        .map(|buffer| buffer.to_vec())
+                }
+
+                /// Calls `f` on the given Read-Write Allow buffer's contents.
+                pub fn with_rw_buffer<R, F: FnOnce(&mut [u8]) -> R>(
+                    &self,
+                    buffer_num: u32,
+                    f: F,
+                ) -> Option<R> {
+                    self.rw_buffers
+                        .borrow_mut()
+                        .get_mut(&buffer_num)
+                        .map(|buffer| f(buffer))
+                }
+            }
+
+            impl libtock_unittest::fake::SyscallDriver for $fake {
+                fn id(&self) -> u32 {
+                    super::DRIVER_NUM
+                }
+
+                fn num_upcalls(&self) -> u32 {
+                    let subscribe_nums: &[u32] = &[$(super::$sub_const),*];
+                    subscribe_nums.iter().max().map_or(0, |max| max + 1)
+                }
+
+                fn command(&self, command_id: u32, argument0: u32, argument1: u32) -> CommandReturn {
+                    self.command_log
+                        .borrow_mut()
+                        .push((command_id, argument0, argument1));
+                    match self.command_returns.borrow().get(&command_id) {
+                        Some(&command_return) => command_return,
+                        None if command_id == 0 => command_return::success(),
+                        None => command_return::failure(ErrorCode::NoSupport),
+                    }
+                }
+
+                fn allow_readonly(
+                    &self,
+                    buffer_num: u32,
+                    buffer: RoAllowBuffer,
+                ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+                    let buffer_nums: &[u32] = &[$(super::$ro_const),*];
+                    if !buffer_nums.contains(&buffer_num) {
+                        return Err((buffer, ErrorCode::NoSupport));
+                    }
+                    Ok(self
+                        .ro_buffers
+                        .borrow_mut()
+                        .insert(buffer_num, buffer)
+                        .unwrap_or_default())
+                }
+
+                fn allow_readwrite(
+                    &self,
+                    buffer_num: u32,
+                    buffer: RwAllowBuffer,
+                ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+                    let buffer_nums: &[u32] = &[$(super::$rw_const),*];
+                    if !buffer_nums.contains(&buffer_num) {
+                        return Err((buffer, ErrorCode::NoSupport));
+                    }
+                    Ok(self
+                        .rw_buffers
+                        .borrow_mut()
+                        .insert(buffer_num, buffer)
+                        .unwrap_or_default())
+                }
+            }
+        }
+    };
+
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident;
+
+        const DRIVER_NUM: u32 = $driver_num:expr;
+
+        commands $commands:tt
+        subscribes $subscribes:tt
+        allow_ro $allow_ro:tt
+        allow_rw $allow_rw:tt
+
+        fake $fake:ident;
+    ) => {
+        $crate::driver! {
+            @api ($(#[$attr])*) $vis $name ($driver_num)
+            $commands $subscribes $allow_ro $allow_rw
+        }
+        $crate::driver! { @fake $fake $commands $subscribes $allow_ro $allow_rw }
+    };
+
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident;
+
+        const DRIVER_NUM: u32 = $driver_num:expr;
+
+        commands $commands:tt
+        subscribes $subscribes:tt
+        allow_ro $allow_ro:tt
+        allow_rw $allow_rw:tt
+    ) => {
+        $crate::driver! {
+            @api ($(#[$attr])*) $vis $name ($driver_num)
+            $commands $subscribes $allow_ro $allow_rw
+        }
+    };
+}
+
+// Expands to a command's success type, which defaults to ().
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __driver_return_type {
+    () => {
+        ()
+    };
+    ($ret:ty) => {
+        $ret
+    };
+}
+
+// Expands to a command's (argument0, argument1) pair, filling in missing
+// arguments with 0.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __driver_command_args {
+    () => {
+        (0, 0)
+    };
+    ($argument0:ident) => {
+        ($argument0.into(), 0)
+    };
+    ($argument0:ident, $argument1:ident) => {
+        ($argument0.into(), $argument1.into())
+    };
+}
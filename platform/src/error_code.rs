@@ -270,6 +270,12 @@ impl fmt::Debug for ErrorCode {
     }
 }
 
+impl From<ErrorCode> for u32 {
+    fn from(error_code: ErrorCode) -> u32 {
+        error_code as u32
+    }
+}
+
 impl TryFrom<u32> for ErrorCode {
     type Error = NotAnErrorCode;
 
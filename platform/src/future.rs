@@ -0,0 +1,207 @@
+//! `future` contains an async/await interface to Tock's upcalls, as well as a
+//! minimal executor that runs a single future to completion.
+
+use crate::{share, subscribe, ErrorCode, Subscribe, Syscalls, Upcall};
+use core::cell::Cell;
+use core::future::Future;
+use core::marker::{PhantomData, PhantomPinned};
+use core::mem::ManuallyDrop;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+// -----------------------------------------------------------------------------
+// `SubscribeFuture`
+// -----------------------------------------------------------------------------
+
+/// A future that resolves with the upcall's arguments when the specified
+/// upcall is invoked.
+///
+/// When it is first polled, `SubscribeFuture` subscribes to the upcall, then
+/// calls `start`. `start` should start the operation that will result in the
+/// upcall, so that the upcall cannot be delivered before it is subscribed.
+/// If the subscribe or `start` fails, the future resolves with that error.
+///
+/// The upcall is unsubscribed when the future resolves or is dropped,
+/// whichever happens first.
+///
+/// # Example
+/// ```ignore
+/// let (arg0, arg1, arg2) = SubscribeFuture::<S, DefaultConfig, 3, 0, _>::new(|| {
+///     S::command(3, 1, 0, 0).to_result()
+/// })
+/// .await?;
+/// ```
+pub struct SubscribeFuture<
+    S: Syscalls,
+    CONFIG: subscribe::Config,
+    const DRIVER_NUM: u32,
+    const SUBSCRIBE_NUM: u32,
+    START: FnOnce() -> Result<(), ErrorCode>,
+> {
+    // Contains `start` until the future is first polled.
+    start: Cell<Option<START>>,
+
+    // The upcall registered with the kernel. The kernel holds a pointer to
+    // `upcall` while `subscribed` is true.
+    upcall: FutureUpcall,
+    subscribed: Cell<bool>,
+
+    _syscalls: PhantomData<S>,
+    _config: PhantomData<CONFIG>,
+
+    // The kernel holds a pointer into this future, so it must not move after
+    // it is first polled.
+    _pinned: PhantomPinned,
+}
+
+impl<
+        S: Syscalls,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+        START: FnOnce() -> Result<(), ErrorCode>,
+    > SubscribeFuture<S, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM, START>
+{
+    pub fn new(start: START) -> Self {
+        Self {
+            start: Cell::new(Some(start)),
+            upcall: Default::default(),
+            subscribed: Cell::new(false),
+            _syscalls: PhantomData,
+            _config: PhantomData,
+            _pinned: PhantomPinned,
+        }
+    }
+
+    // Unsubscribes the upcall if it is currently subscribed.
+    fn unsubscribe(&self) {
+        if self.subscribed.replace(false) {
+            S::unsubscribe(DRIVER_NUM, SUBSCRIBE_NUM);
+        }
+    }
+}
+
+impl<
+        S: Syscalls,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+        START: FnOnce() -> Result<(), ErrorCode>,
+    > Future for SubscribeFuture<S, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM, START>
+{
+    type Output = Result<(u32, u32, u32), ErrorCode>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        // We only access the future through a shared reference, because the
+        // kernel accesses `upcall` through a shared reference as well.
+        let this = self.into_ref().get_ref();
+
+        if let Some(start) = this.start.take() {
+            // share::Handle requires the Subscribe to be dropped before the
+            // upcall becomes invalid. Dropping the Subscribe would unsubscribe
+            // immediately, so instead this future unsubscribes when it resolves
+            // or is dropped, which is equivalent.
+            let subscribe = ManuallyDrop::new(Subscribe::default());
+            // Safety: This future is pinned, so `upcall` will remain valid
+            // until this future is dropped, and `Drop::drop` unsubscribes the
+            // upcall. Therefore the upcall is unsubscribed before the 'static
+            // reference becomes dangling, and the 'static reference is not
+            // exposed outside this function.
+            let (handle, upcall) = unsafe {
+                let upcall: &'static FutureUpcall = &*(&this.upcall as *const FutureUpcall);
+                (share::Handle::new(&*subscribe), upcall)
+            };
+            if let Err(error) =
+                S::subscribe::<_, _, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM>(handle, upcall)
+            {
+                return Poll::Ready(Err(error));
+            }
+            this.subscribed.set(true);
+            if let Err(error) = start() {
+                this.unsubscribe();
+                return Poll::Ready(Err(error));
+            }
+        }
+
+        match this.upcall.args.take() {
+            Some(args) => {
+                this.unsubscribe();
+                Poll::Ready(Ok(args))
+            }
+            None => {
+                this.upcall.waker.set(Some(context.waker().clone()));
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<
+        S: Syscalls,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+        START: FnOnce() -> Result<(), ErrorCode>,
+    > Drop for SubscribeFuture<S, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM, START>
+{
+    fn drop(&mut self) {
+        self.unsubscribe();
+    }
+}
+
+// The Upcall registered by SubscribeFuture. Stores the upcall's arguments and
+// wakes the task that is waiting on the future.
+#[derive(Default)]
+struct FutureUpcall {
+    args: Cell<Option<(u32, u32, u32)>>,
+    waker: Cell<Option<Waker>>,
+}
+
+impl Upcall<subscribe::AnyId> for FutureUpcall {
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        self.args.set(Some((arg0, arg1, arg2)));
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Executor
+// -----------------------------------------------------------------------------
+
+/// Runs `future` to completion, and returns its output.
+///
+/// `block_on` is a single-task executor: each time `future` returns
+/// `Poll::Pending`, `block_on` calls `yield_wait` to wait for an upcall, then
+/// polls `future` again. As a result, `future` must only become ready as a
+/// result of upcalls (which `SubscribeFuture` satisfies), and wakeups that
+/// happen outside of an upcall are not observed until the next upcall runs.
+pub fn block_on<S: Syscalls, F: Future>(future: F) -> F::Output {
+    let mut future = future;
+    // Safety: `future` is shadowed, so it cannot be moved after it is pinned.
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+    // Safety: NOOP_WAKER_VTABLE's functions do not use the data pointer, so
+    // they satisfy RawWaker's contract for any data pointer.
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        S::yield_wait();
+    }
+}
+
+// block_on re-polls its future after every upcall, so its waker does not need
+// to do anything.
+const NOOP_WAKER_VTABLE: RawWakerVTable = RawWakerVTable::new(
+    |_| noop_raw_waker(), // clone
+    |_| {},               // wake
+    |_| {},               // wake_by_ref
+    |_| {},               // drop
+);
+
+fn noop_raw_waker() -> RawWaker {
+    RawWaker::new(core::ptr::null(), &NOOP_WAKER_VTABLE)
+}
@@ -3,33 +3,41 @@
 
 pub mod allow_ro;
 pub mod allow_rw;
+pub mod allow_ur;
 pub mod command_return;
 mod constants;
 mod default_config;
+mod driver;
 mod error_code;
 pub mod exit_on_drop;
+pub mod future;
 mod raw_syscalls;
 mod register;
 pub mod return_variant;
 pub mod share;
+mod single_threaded;
 pub mod subscribe;
 mod syscalls;
 mod syscalls_impl;
 mod termination;
+pub mod upcall_queue;
 mod yield_types;
 
 pub use allow_ro::AllowRo;
 pub use allow_rw::AllowRw;
+pub use allow_ur::AllowUr;
 pub use command_return::CommandReturn;
-pub use constants::{exit_id, syscall_class, yield_id};
+pub use constants::{exit_id, memop_id, syscall_class, yield_id};
 pub use default_config::DefaultConfig;
 pub use error_code::ErrorCode;
 pub use raw_syscalls::RawSyscalls;
 pub use register::Register;
 pub use return_variant::ReturnVariant;
+pub use single_threaded::SingleThreaded;
 pub use subscribe::{Subscribe, Upcall};
 pub use syscalls::Syscalls;
-pub use termination::Termination;
+pub use termination::{Restart, Termination};
+pub use upcall_queue::UpcallQueue;
 pub use yield_types::YieldNoWaitReturn;
 
 #[cfg(test)]
@@ -37,3 +45,6 @@ mod command_return_tests;
 
 #[cfg(test)]
 mod error_code_tests;
+
+#[cfg(test)]
+mod upcall_queue_tests;
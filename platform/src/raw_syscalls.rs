@@ -57,6 +57,7 @@ use crate::Register;
 //
 //   unsafe fn yield1([Reg; 1]) -> [Reg; 4];
 //   unsafe fn yield2([Reg; 2]) -> [Reg; 4];
+//   unsafe fn yield3([Reg; 3]) -> [Reg; 4];
 //   unsafe fn syscall1<const CLASS: usize>([Reg; 1]) -> [Reg; 4];
 //   unsafe fn syscall2<const CLASS: usize>([Reg; 2]) -> [Reg; 4];
 //   unsafe fn syscall4<const CLASS: usize>([Reg; 4]) -> [Reg; 4];
@@ -105,6 +106,30 @@ pub unsafe trait RawSyscalls: Sized {
     /// It has the same safety invariants as the underlying system call.
     unsafe fn yield2(_: [Register; 2]);
 
+    // yield3 can only be used to call `yield-wait-for`. `yield-wait-for` does
+    // not invoke an upcall; instead, it returns the arguments of the upcall it
+    // waited for in r0-r2. As no process code runs during the system call, it
+    // has the same clobbers as the non-yield system calls.
+    //
+    // yield3 should:
+    //     1. Call syscall class 0
+    //     2. Pass in r0-r2 as inlateout registers.
+    //     3. Mark r3 as a lateout clobber.
+    //     4. Have all of the following options:
+    //            preserves_flags
+    //            nostack
+    //     5. NOT have any of the following options:
+    //            pure      (yield has side effects)
+    //            nomem     (the kernel can write to Read-Write Allow buffers
+    //                       while the process waits)
+    //            readonly  (incompatible with writes to allowed buffers)
+    //            noreturn  (yield is expected to return)
+    /// `yield3` should only be called by `libtock_platform`.
+    /// # Safety
+    /// yield3 may only be used for `yield-wait-for`. It has the same safety
+    /// invariants as the underlying system call.
+    unsafe fn yield3(_: [Register; 3]) -> [Register; 3];
+
     // syscall1 is only used to invoke Memop operations. Because there are no
     // Memop commands that set r2 or r3, raw_syscall1 only needs to return r0
     // and r1.
@@ -195,5 +220,11 @@ pub unsafe trait RawSyscalls: Sized {
     /// boundary. In particular, that means there MUST NOT be a reference
     /// overlapping the passed buffer, until the buffer has been returned by a
     /// Read-Write Allow call.
+    ///
+    /// For Userspace-Readable Allow, the aliasing invariants on the buffer are
+    /// equivalent to passing a `&[Cell<u8>]` reference across the system call
+    /// boundary. In particular, that means there MUST NOT be a `&[u8]` or
+    /// `&mut [u8]` reference overlapping the passed buffer, until the buffer
+    /// has been returned by a Userspace-Readable Allow call.
     unsafe fn syscall4<const CLASS: usize>(_: [Register; 4]) -> [Register; 4];
 }
@@ -0,0 +1,26 @@
+/// Implements `share::List`, `share::SplittableHandle`, and `share::StaticList`
+/// on arrays.
+use crate::share::{Handle, List, SplittableHandle, StaticList};
+
+// Because List depends on Default, which is only implemented for arrays of
+// <= 32 elements, these impls only apply to arrays of up to length 32.
+impl<T: List, const N: usize> List for [T; N] where [T; N]: Default {}
+
+impl<'handle, T: List + 'handle, const N: usize> SplittableHandle<'handle> for [T; N]
+where
+    [T; N]: Default,
+{
+    type SplitHandles = [Handle<'handle, T>; N];
+
+    fn split(handle: Handle<'handle, Self>) -> Self::SplitHandles {
+        // Safety: handle guarantees that an instance of Self exists and will be
+        // cleaned up before it becomes invalid. Self is an array, and the type
+        // we are changing handle into is its element type, so when the array is
+        // cleaned up the elements will be cleaned up as well.
+        [(); N].map(|()| unsafe { handle.change_type::<T>() })
+    }
+}
+
+// Safety: Every element of the array is a StaticList, so none of them need to
+// be dropped for soundness.
+unsafe impl<T: StaticList, const N: usize> StaticList for [T; N] where [T; N]: Default {}
@@ -9,6 +9,14 @@ use core::marker::PhantomData;
 ///
 /// `Handle`s are used to call system calls, and should generally be created by
 /// using `share::scope`.
+///
+/// The second guarantee exists so that the objects in the List are unshared
+/// before the List's 'share lifetime ends. If 'share is `'static`, then the
+/// shared objects remain valid for the rest of the process' execution, so it
+/// does not matter whether the List is ever dropped. `Handle::new_static`
+/// relies on this to construct `Handle`s to `StaticList`s without constructing
+/// a List, which allows `'static` objects to be shared outside of
+/// `share::scope`.
 pub struct Handle<'handle, L: List> {
     // Handle acts like a &'handle L, so set its variance accordingly. Note that
     // the most important lifetime -- the lifetime the share can live for -- is
@@ -54,8 +62,8 @@ impl<'handle, L: List> Handle<'handle, L> {
     }
 
     /// Splits this `Handle` into a list of handles to sub-lists of `L`. Used
-    /// when `L` is a tuple of shares, to obtain handles to the individual
-    /// shares.
+    /// when `L` is a tuple or array of shares, to obtain handles to the
+    /// individual shares.
     pub fn split(self) -> L::SplitHandles
     where
         L: SplittableHandle<'handle>,
@@ -64,12 +72,47 @@ impl<'handle, L: List> Handle<'handle, L> {
     }
 }
 
+impl<L: StaticList> Handle<'static, L> {
+    /// Constructs a `Handle` to a `StaticList` without a `share::scope`. This
+    /// can be used to share `'static` objects (such as a buffer obtained from a
+    /// `static mut`) with the kernel for the rest of the process' execution.
+    pub fn new_static() -> Self {
+        // No L is constructed, so no L is dropped. That is sound because the
+        // StaticList contract guarantees that L's shares only borrow 'static
+        // objects, which never become invalid.
+        Handle { _list: PhantomData }
+    }
+}
+
+/// A `share::List` whose shares only borrow objects for the `'static`
+/// lifetime, such as `AllowRw<'static, ...>`. `Handle`s to `StaticList`s can
+/// be constructed by `Handle::new_static`, without a `share::scope`.
+///
+/// Dropping a share only revokes the kernel's access to the object it borrows.
+/// That revocation is needed for soundness when the object is about to become
+/// invalid, which never happens to a `'static` object. A `'static` object may
+/// stay shared for the rest of the process' execution without harm. The wrapper
+/// types (`StaticAllowRw`, `StaticAllowUr`, and `StaticSubscribe`) revoke access
+/// on request, and return the object to the caller.
+///
+/// A static share can also end early. A later share with the same driver and
+/// buffer (or subscribe) numbers replaces it in the kernel. If that share is
+/// scoped, it is revoked when its scope ends, leaving nothing shared with those
+/// numbers. This is sound: revoking access never gives the kernel access to
+/// anything. The static object is no longer shared, so a wrapper's
+/// revoke method may return it. That method's own revocation then does nothing.
+///
+/// # Safety
+/// `Drop::drop` must not be required to run on the List for soundness, given
+/// that every object the List's shares borrow lives for the `'static` lifetime.
+pub unsafe trait StaticList: List + 'static {}
+
 /// A trait implemented by `share::List`s that can be divided into sub-lists.
 /// `SplittableHandle` should not be used directly; instead, callers should use
 /// `Handle::split`.
 pub trait SplittableHandle<'handle>: List {
-    /// SplitHandles should be a tuple of Handle types, i.e. (Handle<>,
-    /// Handle<>, ...).
+    /// SplitHandles should be a tuple or array of Handle types, i.e.
+    /// (Handle<>, Handle<>, ...) or [Handle<>; N].
     type SplitHandles;
 
     /// Split the specified handle into sub-handles. Implementations of `split`
@@ -1,10 +1,11 @@
 //! `share` contains tools for safely sharing objects (such as buffers and
 //! upcalls) with the Tock kernel.
 
+mod array_impls;
 mod handle;
 mod tuple_impls;
 
-pub use handle::{Handle, SplittableHandle};
+pub use handle::{Handle, SplittableHandle, StaticList};
 
 /// Creates a scope in which objects may safely be shared with the kernel.
 pub fn scope<L: List, Output, F: FnOnce(Handle<L>) -> Output>(fcn: F) -> Output {
@@ -17,7 +18,7 @@ pub fn scope<L: List, Output, F: FnOnce(Handle<L>) -> Output>(fcn: F) -> Output
 
 /// A list of objects that may be shared with the kernel. `List` is implemented
 /// for system call types such as `Subscribe`, as well as (potentially-nested)
-/// tuples of such types.
+/// tuples and arrays of such types.
 pub trait List: Default {}
 
 #[cfg(test)]
@@ -99,3 +99,26 @@ fn tuple_impls() {
         },
     );
 }
+
+#[test]
+fn array_impls() {
+    INSTANCE_COUNT.with(|cell| cell.set(0));
+
+    scope(|list: Handle<[InstanceCounter; 0]>| {
+        let _empty: [Handle<InstanceCounter>; 0] = list.split();
+    });
+
+    scope(|list: Handle<[InstanceCounter; 3]>| {
+        assert_eq!(INSTANCE_COUNT.with(|cell| cell.get()), 3);
+        let counters: [Handle<InstanceCounter>; 3] = list.split();
+        assert_eq!(counters.len(), 3);
+    });
+    assert_eq!(INSTANCE_COUNT.with(|cell| cell.get()), 0);
+
+    // Arrays and tuples may be nested within each other.
+    scope(|list: Handle<[((), [InstanceCounter; 2]); 2]>| {
+        let [first, _second] = list.split();
+        let (_empty, counters): (Handle<()>, Handle<[InstanceCounter; 2]>) = first.split();
+        let [_counter1, _counter2] = counters.split();
+    });
+}
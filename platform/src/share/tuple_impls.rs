@@ -1,6 +1,6 @@
-/// Implements `share::List` and `share::SplittableHandle` on tuples of various
-/// sizes.
-use crate::share::{Handle, List, SplittableHandle};
+/// Implements `share::List`, `share::SplittableHandle`, and `share::StaticList`
+/// on tuples of various sizes.
+use crate::share::{Handle, List, SplittableHandle, StaticList};
 
 // Implement List and SplittableHandle on empty tuples, because tuple_impl only
 // works for tuples of size 1 or greater. Empty lists may be useful in generic
@@ -13,9 +13,12 @@ impl<'handle> SplittableHandle<'handle> for () {
     fn split(_handle: Handle<'handle, ()>) {}
 }
 
-// Provides `share::List` and `share::SplittableHandle` impls for tuples of a
-// specific size. tuple_impls! must be provided with a list of names, and will
-// generate impls for tuples of the given length.
+// Safety: An empty list does not share anything.
+unsafe impl StaticList for () {}
+
+// Provides `share::List`, `share::SplittableHandle`, and `share::StaticList`
+// impls for tuples of a specific size. tuple_impls! must be provided with a
+// list of names, and will generate impls for tuples of the given length.
 macro_rules! tuple_impls {
     ($($name:ident),*) => {
         impl<$($name: List),*> List for ($($name),*,) {}
@@ -32,6 +35,10 @@ macro_rules! tuple_impls {
                 ($(unsafe { handle.change_type::<$name>() }),*,)
             }
         }
+
+        // Safety: Every element of the tuple is a StaticList, so none of them
+        // need to be dropped for soundness.
+        unsafe impl<$($name: StaticList),*> StaticList for ($($name),*,) {}
     }
 }
 
@@ -0,0 +1,26 @@
+/// Wraps a value so that it can be stored in a `static` on Tock, even if it is
+/// not `Sync` (e.g. a `Cell`). Tock processes are single-threaded, and upcalls
+/// only run during Yield system calls, so the value is never accessed
+/// concurrently. `SingleThreaded` is only `Sync` when building for Tock;
+/// elsewhere (e.g. in unit tests, which run in parallel threads), use a
+/// thread-local instead.
+pub struct SingleThreaded<T>(T);
+
+impl<T> SingleThreaded<T> {
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> core::ops::Deref for SingleThreaded<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+// Safety: Tock processes are single-threaded, so a SingleThreaded is never
+// accessed concurrently.
+#[cfg(target_os = "none")]
+unsafe impl<T> Sync for SingleThreaded<T> {}
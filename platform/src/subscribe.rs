@@ -1,5 +1,5 @@
-use crate::share::List;
-use crate::Syscalls;
+use crate::share::{Handle, List, StaticList};
+use crate::{ErrorCode, Syscalls};
 
 // -----------------------------------------------------------------------------
 // `Subscribe` struct
@@ -52,6 +52,50 @@ impl<'share, S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32> List
 {
 }
 
+// Safety: A `Subscribe<'static, ...>` only registers `&'static` upcalls.
+unsafe impl<S: Syscalls + 'static, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32> StaticList
+    for Subscribe<'static, S, DRIVER_NUM, SUBSCRIBE_NUM>
+{
+}
+
+// -----------------------------------------------------------------------------
+// `StaticSubscribe` struct
+// -----------------------------------------------------------------------------
+
+/// Registers a `'static` upcall with the kernel, outside of `share::scope`. The
+/// upcall remains registered until `unsubscribe` is called. If a
+/// `StaticSubscribe` is dropped, the upcall remains registered until another
+/// upcall is registered with the same driver and subscribe numbers.
+pub struct StaticSubscribe<S: Syscalls, U: 'static, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32>
+{
+    upcall: &'static U,
+    _syscalls: core::marker::PhantomData<S>,
+}
+
+impl<S: Syscalls + 'static, U: 'static, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32>
+    StaticSubscribe<S, U, DRIVER_NUM, SUBSCRIBE_NUM>
+{
+    /// Registers `upcall` with the kernel.
+    pub fn new<IDS: SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>, CONFIG: Config>(
+        upcall: &'static U,
+    ) -> Result<Self, ErrorCode>
+    where
+        U: Upcall<IDS>,
+    {
+        S::subscribe::<IDS, U, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM>(Handle::new_static(), upcall)?;
+        Ok(Self {
+            upcall,
+            _syscalls: core::marker::PhantomData,
+        })
+    }
+
+    /// Unregisters the upcall, and returns it.
+    pub fn unsubscribe(self) -> &'static U {
+        S::unsubscribe(DRIVER_NUM, SUBSCRIBE_NUM);
+        self.upcall
+    }
+}
+
 // -----------------------------------------------------------------------------
 // `Upcall` trait
 // -----------------------------------------------------------------------------
@@ -147,6 +191,90 @@ fn upcall_impls() {
     assert_eq!(cell_three.get(), Some((1, 2, 3)));
 }
 
+// -----------------------------------------------------------------------------
+// Upcall adapters
+// -----------------------------------------------------------------------------
+
+/// An `Upcall` implementation that calls the contained closure with the
+/// upcall's arguments.
+///
+/// ```ignore
+/// let upcall = FnUpcall(|arg0, arg1, arg2| {
+///     // make use of the upcall's arguments
+/// });
+/// ```
+pub struct FnUpcall<F: Fn(u32, u32, u32)>(pub F);
+
+impl<F: Fn(u32, u32, u32)> Upcall<AnyId> for FnUpcall<F> {
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        self.0(arg0, arg1, arg2)
+    }
+}
+
+/// `Decode` converts an upcall's raw arguments into a domain type. Drivers
+/// implement `Decode` for each upcall they provide, and set `SupportedIds` to
+/// the upcall's subscribe ID (generally `OneId`), so that `DecodedUpcall`s can
+/// only be subscribed to the upcall they decode.
+pub trait Decode {
+    type SupportedIds;
+    type Args;
+
+    fn decode(arg0: u32, arg1: u32, arg2: u32) -> Self::Args;
+}
+
+/// An `Upcall` implementation that decodes the upcall's arguments using `D`,
+/// then passes the decoded arguments to the contained closure.
+///
+/// ```ignore
+/// let upcall = DecodedUpcall::<ButtonEvent, _>::new(|(button_index, state)| {
+///     // make use of the button's state
+/// });
+/// ```
+pub struct DecodedUpcall<D: Decode, F: Fn(D::Args)> {
+    f: F,
+    _decode: core::marker::PhantomData<D>,
+}
+
+impl<D: Decode, F: Fn(D::Args)> DecodedUpcall<D, F> {
+    pub fn new(f: F) -> Self {
+        Self {
+            f,
+            _decode: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<D: Decode, F: Fn(D::Args)> Upcall<D::SupportedIds> for DecodedUpcall<D, F> {
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        (self.f)(D::decode(arg0, arg1, arg2))
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn upcall_adapters() {
+    use core::cell::Cell;
+
+    let args = Cell::new(None);
+    let fn_upcall = FnUpcall(|arg0, arg1, arg2| args.set(Some((arg0, arg1, arg2))));
+    fn_upcall.upcall(1, 2, 3);
+    assert_eq!(args.get(), Some((1, 2, 3)));
+
+    struct Sum;
+    impl Decode for Sum {
+        type SupportedIds = OneId<1, 2>;
+        type Args = u64;
+
+        fn decode(arg0: u32, arg1: u32, arg2: u32) -> u64 {
+            arg0 as u64 + arg1 as u64 + arg2 as u64
+        }
+    }
+    let sum = Cell::new(None);
+    let decoded_upcall = DecodedUpcall::<Sum, _>::new(|value| sum.set(Some(value)));
+    Upcall::<OneId<1, 2>>::upcall(&decoded_upcall, u32::MAX, 2, 3);
+    assert_eq!(sum.get(), Some(u32::MAX as u64 + 5));
+}
+
 // -----------------------------------------------------------------------------
 // `Config` trait
 // -----------------------------------------------------------------------------
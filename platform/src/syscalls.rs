@@ -1,7 +1,8 @@
 use crate::{
-    allow_ro, allow_rw, share, subscribe, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls,
-    Subscribe, Upcall, YieldNoWaitReturn,
+    allow_ro, allow_rw, allow_ur, share, subscribe, AllowRo, AllowRw, AllowUr, CommandReturn,
+    ErrorCode, RawSyscalls, Subscribe, Upcall, YieldNoWaitReturn,
 };
+use core::cell::Cell;
 
 /// `Syscalls` provides safe abstractions over Tock's system calls. It is
 /// implemented for `libtock_runtime::TockSyscalls` and
@@ -20,6 +21,12 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// callback, then returns.
     fn yield_wait();
 
+    /// Puts the process to sleep until the upcall with the given driver number
+    /// and subscribe number is scheduled, then returns the upcall's arguments.
+    /// No upcall is invoked: the upcall does not need to be subscribed, and if
+    /// it is, the subscribed upcall is not run.
+    fn yield_wait_for(driver_num: u32, subscribe_num: u32) -> (u32, u32, u32);
+
     // -------------------------------------------------------------------------
     // Subscribe
     // -------------------------------------------------------------------------
@@ -77,7 +84,68 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// `unallow_ro` does nothing.
     fn unallow_ro(driver_num: u32, buffer_num: u32);
 
-    // TODO: Add memop() methods.
+    // -------------------------------------------------------------------------
+    // Userspace-Readable Allow
+    // -------------------------------------------------------------------------
+
+    /// Shares a buffer with the kernel that the process may continue to read
+    /// while it is shared.
+    fn allow_ur<'share, CONFIG: allow_ur::Config, const DRIVER_NUM: u32, const BUFFER_NUM: u32>(
+        allow_ur: share::Handle<AllowUr<'share, Self, DRIVER_NUM, BUFFER_NUM>>,
+        buffer: &'share [Cell<u8>],
+    ) -> Result<(), ErrorCode>;
+
+    /// Revokes the kernel's access to the buffer with the given ID, overwriting
+    /// it with a zero buffer. If no buffer is shared with the given ID,
+    /// `unallow_ur` does nothing.
+    fn unallow_ur(driver_num: u32, buffer_num: u32);
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+
+    /// Sets the process break to `address`.
+    ///
+    /// # Safety
+    /// Memory between the new break and the old break becomes inaccessible if
+    /// the break is lowered. The caller must guarantee that no reachable object
+    /// lives above `address`.
+    unsafe fn memop_brk(address: *mut u8) -> Result<(), ErrorCode>;
+
+    /// Moves the process break by `increment` bytes, and returns the previous
+    /// process break.
+    ///
+    /// # Safety
+    /// Memory between the new break and the old break becomes inaccessible if
+    /// `increment` is negative. The caller must guarantee that no reachable
+    /// object lives above the new break.
+    unsafe fn memop_sbrk(increment: i32) -> Result<*mut u8, ErrorCode>;
+
+    /// Returns the address of the start of the process' RAM region.
+    fn memop_process_ram_start() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address one past the end of the process' RAM region
+    /// (including the grant region).
+    fn memop_process_ram_end() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address of the start of the process' flash region (which
+    /// begins with the TBF header).
+    fn memop_flash_start() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the address one past the end of the process' flash region.
+    fn memop_flash_end() -> Result<*const u8, ErrorCode>;
+
+    /// Returns the lowest address of the grant region, which is the highest
+    /// address the process break can be moved to.
+    fn memop_grant_region_start() -> Result<*const u8, ErrorCode>;
+
+    /// Tells the kernel where the top of the process' stack is. This is only
+    /// used by the kernel to produce more useful debugging output.
+    fn memop_debug_stack_start(stack_top: *const u8) -> Result<(), ErrorCode>;
+
+    /// Tells the kernel where the process' heap starts. This is only used by
+    /// the kernel to produce more useful debugging output.
+    fn memop_debug_heap_start(heap_start: *const u8) -> Result<(), ErrorCode>;
 
     // -------------------------------------------------------------------------
     // Exit
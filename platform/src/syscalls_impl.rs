@@ -1,10 +1,11 @@
 //! Implements `Syscalls` for all types that implement `RawSyscalls`.
 
 use crate::{
-    allow_ro, allow_rw, exit_id, exit_on_drop, return_variant, share, subscribe, syscall_class,
-    yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register, ReturnVariant,
-    Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
+    allow_ro, allow_rw, allow_ur, exit_id, exit_on_drop, memop_id, return_variant, share,
+    subscribe, syscall_class, yield_id, AllowRo, AllowRw, AllowUr, CommandReturn, ErrorCode,
+    RawSyscalls, Register, ReturnVariant, Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
 };
+use core::cell::Cell;
 
 impl<S: RawSyscalls> Syscalls for S {
     // -------------------------------------------------------------------------
@@ -35,6 +36,20 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 
+    fn yield_wait_for(driver_num: u32, subscribe_num: u32) -> (u32, u32, u32) {
+        // Safety: yield3's documentation indicates it can be used to call
+        // yield-wait-for. yield-wait-for does not run any upcalls and cannot
+        // trigger undefined behavior on its own in any other way.
+        let [r0, r1, r2] = unsafe {
+            Self::yield3([
+                yield_id::WAIT_FOR.into(),
+                driver_num.into(),
+                subscribe_num.into(),
+            ])
+        };
+        (r0.as_u32(), r1.as_u32(), r2.as_u32())
+    }
+
     // -------------------------------------------------------------------------
     // Subscribe
     // -------------------------------------------------------------------------
@@ -352,6 +367,186 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 
+    // -------------------------------------------------------------------------
+    // Userspace-Readable Allow
+    // -------------------------------------------------------------------------
+
+    fn allow_ur<'share, CONFIG: allow_ur::Config, const DRIVER_NUM: u32, const BUFFER_NUM: u32>(
+        _allow_ur: share::Handle<AllowUr<'share, Self, DRIVER_NUM, BUFFER_NUM>>,
+        buffer: &'share [Cell<u8>],
+    ) -> Result<(), ErrorCode> {
+        // Inner function that does the majority of the work. This is not
+        // monomorphized over DRIVER_NUM and BUFFER_NUM to keep code size small.
+        //
+        // Safety: A share::Handle<AllowUr<'share, S, driver_num, buffer_num>>
+        // must exist, and `buffer` must last for at least the 'share lifetime.
+        unsafe fn inner<S: Syscalls, CONFIG: allow_ur::Config>(
+            driver_num: u32,
+            buffer_num: u32,
+            buffer: &[Cell<u8>],
+        ) -> Result<(), ErrorCode> {
+            // Safety: syscall4's documentation indicates it can be used to call
+            // Userspace-Readable Allow. These arguments follow TRD104.
+            let [r0, r1, r2, _] = unsafe {
+                S::syscall4::<{ syscall_class::ALLOW_UR }>([
+                    driver_num.into(),
+                    buffer_num.into(),
+                    buffer.as_ptr().into(),
+                    buffer.len().into(),
+                ])
+            };
+
+            let return_variant: ReturnVariant = r0.as_u32().into();
+            // Userspace-Readable Allow returns either Success with 2 U32 or
+            // Failure with 2 U32. We compare against Failure with 2 U32 for the
+            // same reasons as in allow_rw.
+            if return_variant == return_variant::FAILURE_2_U32 {
+                // Safety: TRD 104 guarantees that if r0 is Failure with 2 U32,
+                // then r1 will contain a valid error code. ErrorCode is
+                // designed to be safely transmuted directly from a kernel error
+                // code.
+                return Err(unsafe { core::mem::transmute(r1.as_u32() as u16) });
+            }
+
+            // r0 indicates Success with 2 u32s. Confirm a zero buffer was
+            // returned, and it if wasn't then call the configured function.
+            // We're relying on the optimizer to remove this branch if
+            // returned_nozero_buffer is a no-op.
+            let returned_buffer: (usize, usize) = (r1.into(), r2.into());
+            if returned_buffer != (0, 0) {
+                CONFIG::returned_nonzero_buffer(driver_num, buffer_num);
+            }
+            Ok(())
+        }
+
+        // Safety: The presence of the share::Handle<AllowUr<'share, ...>>
+        // guarantees that an AllowUr exists and will clean up this Allow ID
+        // before the 'share lifetime ends.
+        unsafe { inner::<Self, CONFIG>(DRIVER_NUM, BUFFER_NUM, buffer) }
+    }
+
+    fn unallow_ur(driver_num: u32, buffer_num: u32) {
+        unsafe {
+            // syscall4's documentation indicates it can be used to call
+            // Userspace-Readable Allow. The buffer passed has 0 length, which
+            // cannot cause undefined behavior on its own.
+            Self::syscall4::<{ syscall_class::ALLOW_UR }>([
+                driver_num.into(),
+                buffer_num.into(),
+                0usize.into(),
+                0usize.into(),
+            ]);
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+
+    unsafe fn memop_brk(address: *mut u8) -> Result<(), ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop operations that take an argument. Moving the break is only
+        // unsafe if it makes reachable objects inaccessible, which our caller
+        // guarantees does not happen.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([memop_id::BRK.into(), address.into()])
+        };
+        memop_result(registers).map(|_| ())
+    }
+
+    unsafe fn memop_sbrk(increment: i32) -> Result<*mut u8, ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop operations that take an argument. Moving the break is only
+        // unsafe if it makes reachable objects inaccessible, which our caller
+        // guarantees does not happen.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([
+                memop_id::SBRK.into(),
+                (increment as u32).into(),
+            ])
+        };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_process_ram_start() -> Result<*const u8, ErrorCode> {
+        // Safety: syscall1's documentation indicates it can be used to call
+        // Memop operations that do not take an argument. This operation only
+        // queries the process' memory layout, and cannot trigger undefined
+        // behavior on its own.
+        let registers = unsafe {
+            Self::syscall1::<{ syscall_class::MEMOP }>([memop_id::PROCESS_RAM_START.into()])
+        };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_process_ram_end() -> Result<*const u8, ErrorCode> {
+        // Safety: syscall1's documentation indicates it can be used to call
+        // Memop operations that do not take an argument. This operation only
+        // queries the process' memory layout, and cannot trigger undefined
+        // behavior on its own.
+        let registers = unsafe {
+            Self::syscall1::<{ syscall_class::MEMOP }>([memop_id::PROCESS_RAM_END.into()])
+        };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_flash_start() -> Result<*const u8, ErrorCode> {
+        // Safety: syscall1's documentation indicates it can be used to call
+        // Memop operations that do not take an argument. This operation only
+        // queries the process' memory layout, and cannot trigger undefined
+        // behavior on its own.
+        let registers =
+            unsafe { Self::syscall1::<{ syscall_class::MEMOP }>([memop_id::FLASH_START.into()]) };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_flash_end() -> Result<*const u8, ErrorCode> {
+        // Safety: syscall1's documentation indicates it can be used to call
+        // Memop operations that do not take an argument. This operation only
+        // queries the process' memory layout, and cannot trigger undefined
+        // behavior on its own.
+        let registers =
+            unsafe { Self::syscall1::<{ syscall_class::MEMOP }>([memop_id::FLASH_END.into()]) };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_grant_region_start() -> Result<*const u8, ErrorCode> {
+        // Safety: syscall1's documentation indicates it can be used to call
+        // Memop operations that do not take an argument. This operation only
+        // queries the process' memory layout, and cannot trigger undefined
+        // behavior on its own.
+        let registers = unsafe {
+            Self::syscall1::<{ syscall_class::MEMOP }>([memop_id::GRANT_REGION_START.into()])
+        };
+        memop_result(registers).map(Into::into)
+    }
+
+    fn memop_debug_stack_start(stack_top: *const u8) -> Result<(), ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop operations that take an argument. The debug hints are only
+        // recorded by the kernel, and cannot trigger undefined behavior.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([
+                memop_id::DEBUG_STACK_START.into(),
+                stack_top.into(),
+            ])
+        };
+        memop_result(registers).map(|_| ())
+    }
+
+    fn memop_debug_heap_start(heap_start: *const u8) -> Result<(), ErrorCode> {
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop operations that take an argument. The debug hints are only
+        // recorded by the kernel, and cannot trigger undefined behavior.
+        let registers = unsafe {
+            Self::syscall2::<{ syscall_class::MEMOP }>([
+                memop_id::DEBUG_HEAP_START.into(),
+                heap_start.into(),
+            ])
+        };
+        memop_result(registers).map(|_| ())
+    }
+
     // -------------------------------------------------------------------------
     // Exit
     // -------------------------------------------------------------------------
@@ -383,3 +578,22 @@ impl<S: RawSyscalls> Syscalls for S {
         }
     }
 }
+
+// Converts the registers returned by a Memop call into a Result. On success, r1
+// is returned; Memop operations that return Success (rather than Success with
+// u32) leave r1 unspecified, so their callers ignore it. This is not generic
+// over the Syscalls implementation, to keep code size small.
+fn memop_result([r0, r1]: [Register; 2]) -> Result<Register, ErrorCode> {
+    let return_variant: ReturnVariant = r0.as_u32().into();
+    // TRD 104 guarantees that Memop returns either Failure or a success
+    // variant. As with the other system calls, we compare against the failure
+    // variant, because incorrectly assuming success is safer than incorrectly
+    // assuming r1 contains an error code.
+    if return_variant == return_variant::FAILURE {
+        // Safety: TRD 104 guarantees that if r0 is Failure, then r1 will
+        // contain a valid error code. ErrorCode is designed to be safely
+        // transmuted directly from a kernel error code.
+        return Err(unsafe { core::mem::transmute(r1.as_u32() as u16) });
+    }
+    Ok(r1)
+}
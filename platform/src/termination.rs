@@ -1,7 +1,7 @@
 //! Definition of the Termination trait. The main() function (set using set_main!())
 //! must return a type that implements Termination.
 
-use crate::{ErrorCode, Syscalls};
+use crate::Syscalls;
 
 pub trait Termination {
     fn complete<S: Syscalls>(self) -> !;
@@ -13,12 +13,43 @@ impl Termination for () {
     }
 }
 
-impl Termination for Result<(), ErrorCode> {
+/// Reports errors through `exit_terminate`, using the error's conversion into
+/// `u32` as the exit code. Returns an exit code of 0 on success.
+impl<E: Into<u32>> Termination for Result<(), E> {
     fn complete<S: Syscalls>(self) -> ! {
-        let exit_code = match self {
-            Ok(()) => 0,
-            Err(ec) => ec as u32,
-        };
-        S::exit_terminate(exit_code);
+        S::exit_terminate(result_exit_code(self));
+    }
+}
+
+/// A wrapper around main()'s return value that indicates the process should be
+/// restarted when main() returns. `Restart(value)` calls `exit_restart` with
+/// the exit code that `value` would have passed to `exit_terminate`.
+///
+/// # Example
+/// ```ignore
+/// fn main() -> Restart<Result<(), ErrorCode>> {
+///     Restart(run())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Restart<T>(pub T);
+
+impl Termination for Restart<()> {
+    fn complete<S: Syscalls>(self) -> ! {
+        S::exit_restart(0)
+    }
+}
+
+impl<E: Into<u32>> Termination for Restart<Result<(), E>> {
+    fn complete<S: Syscalls>(self) -> ! {
+        S::exit_restart(result_exit_code(self.0));
+    }
+}
+
+// Converts a Result returned by main() into an exit code.
+fn result_exit_code<E: Into<u32>>(result: Result<(), E>) -> u32 {
+    match result {
+        Ok(()) => 0,
+        Err(error) => error.into(),
     }
 }
@@ -0,0 +1,123 @@
+use crate::subscribe::AnyId;
+use crate::Upcall;
+use core::cell::Cell;
+
+/// An `Upcall` implementation that records every invocation in a fixed-capacity
+/// queue, so that upcalls that arrive between checks are not lost. Unlike the
+/// `Cell<Option<...>>` `Upcall` implementations, `UpcallQueue` does not
+/// overwrite earlier arguments when it is invoked again.
+///
+/// `UpcallQueue` stores up to `N` sets of upcall arguments. If the upcall is
+/// invoked while the queue is full, the new arguments are discarded and the
+/// overflow counter is incremented.
+///
+/// # Example
+/// ```ignore
+/// let events: UpcallQueue<4> = UpcallQueue::new();
+/// share::scope(|subscribe| {
+///     S::subscribe::<_, _, DefaultConfig, 3, 0>(subscribe, &events)?;
+///     loop {
+///         S::yield_wait();
+///         for (button_index, state, _) in events.drain() {
+///             // handle the event
+///         }
+///     }
+/// });
+/// ```
+pub struct UpcallQueue<const N: usize> {
+    // Ring buffer of upcall arguments. The queued entries are
+    // entries[head..head + len], wrapping around at N.
+    entries: [Cell<(u32, u32, u32)>; N],
+    head: Cell<usize>,
+    len: Cell<usize>,
+    overflow_count: Cell<u32>,
+}
+
+impl<const N: usize> UpcallQueue<N> {
+    // Used to initialize `entries`, as Cell is not Copy. Each use of the
+    // constant creates a new Cell, which is the intended behavior.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY_ENTRY: Cell<(u32, u32, u32)> = Cell::new((0, 0, 0));
+
+    pub const fn new() -> Self {
+        Self {
+            entries: [Self::EMPTY_ENTRY; N],
+            head: Cell::new(0),
+            len: Cell::new(0),
+            overflow_count: Cell::new(0),
+        }
+    }
+
+    /// Removes and returns the arguments of the oldest queued upcall, or
+    /// returns `None` if the queue is empty.
+    pub fn pop(&self) -> Option<(u32, u32, u32)> {
+        let len = self.len.get();
+        if len == 0 {
+            return None;
+        }
+        let head = self.head.get();
+        self.head.set((head + 1) % N);
+        self.len.set(len - 1);
+        Some(self.entries[head].get())
+    }
+
+    /// Returns an iterator that pops queued upcalls, oldest first. Upcalls that
+    /// are invoked while the iterator is in use are returned by the iterator as
+    /// well.
+    pub fn drain(&self) -> Drain<'_, N> {
+        Drain { queue: self }
+    }
+
+    /// Returns the number of queued upcalls.
+    pub fn len(&self) -> usize {
+        self.len.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len.get() == 0
+    }
+
+    /// Returns the number of upcalls that were discarded because the queue was
+    /// full. Saturates at `u32::MAX`.
+    pub fn overflow_count(&self) -> u32 {
+        self.overflow_count.get()
+    }
+
+    /// Returns the overflow count and resets it to 0.
+    pub fn take_overflow_count(&self) -> u32 {
+        self.overflow_count.replace(0)
+    }
+}
+
+impl<const N: usize> Default for UpcallQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Upcall<AnyId> for UpcallQueue<N> {
+    fn upcall(&self, arg0: u32, arg1: u32, arg2: u32) {
+        let len = self.len.get();
+        if len == N {
+            self.overflow_count
+                .set(self.overflow_count.get().saturating_add(1));
+            return;
+        }
+        self.entries[(self.head.get() + len) % N].set((arg0, arg1, arg2));
+        self.len.set(len + 1);
+    }
+}
+
+/// An iterator that pops upcalls off an `UpcallQueue`. Returned by
+/// `UpcallQueue::drain`.
+pub struct Drain<'q, const N: usize> {
+    queue: &'q UpcallQueue<N>,
+}
+
+impl<'q, const N: usize> Iterator for Drain<'q, N> {
+    type Item = (u32, u32, u32);
+
+    fn next(&mut self) -> Option<(u32, u32, u32)> {
+        self.queue.pop()
+    }
+}
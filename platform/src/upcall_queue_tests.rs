@@ -0,0 +1,50 @@
+use crate::{Upcall, UpcallQueue};
+
+#[test]
+fn fifo_order() {
+    let queue: UpcallQueue<3> = UpcallQueue::new();
+    assert!(queue.is_empty());
+    assert_eq!(queue.pop(), None);
+
+    queue.upcall(1, 2, 3);
+    queue.upcall(4, 5, 6);
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pop(), Some((1, 2, 3)));
+
+    // Wrap around the end of the ring buffer.
+    queue.upcall(7, 8, 9);
+    queue.upcall(10, 11, 12);
+    assert_eq!(queue.len(), 3);
+    assert_eq!(
+        queue.drain().collect::<Vec<_>>(),
+        [(4, 5, 6), (7, 8, 9), (10, 11, 12)]
+    );
+    assert!(queue.is_empty());
+    assert_eq!(queue.overflow_count(), 0);
+}
+
+#[test]
+fn overflow() {
+    let queue: UpcallQueue<2> = Default::default();
+    queue.upcall(1, 0, 0);
+    queue.upcall(2, 0, 0);
+    queue.upcall(3, 0, 0);
+    queue.upcall(4, 0, 0);
+    assert_eq!(queue.overflow_count(), 2);
+
+    // The oldest events are kept, and the overflowing events are discarded.
+    assert_eq!(queue.pop(), Some((1, 0, 0)));
+    queue.upcall(5, 0, 0);
+    assert_eq!(queue.drain().collect::<Vec<_>>(), [(2, 0, 0), (5, 0, 0)]);
+
+    assert_eq!(queue.take_overflow_count(), 2);
+    assert_eq!(queue.overflow_count(), 0);
+}
+
+#[test]
+fn zero_capacity() {
+    let queue: UpcallQueue<0> = UpcallQueue::new();
+    queue.upcall(1, 2, 3);
+    assert_eq!(queue.pop(), None);
+    assert_eq!(queue.overflow_count(), 1);
+}
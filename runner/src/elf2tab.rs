@@ -1,22 +1,23 @@
 use super::Cli;
 use std::fs::{metadata, remove_file};
 use std::io::ErrorKind;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 // Converts the ELF file specified on the command line into TBF and TAB files,
 // and returns the paths to those files.
 pub fn convert_elf(cli: &Cli) -> OutFiles {
-    let package_name = cli.elf.file_stem().expect("ELF must be a file");
+    let options = read_tbf_options(&cli.elf, cli.verbose);
+    let package_name = match options.app_name {
+        Some(ref app_name) => app_name.as_ref(),
+        None => cli.elf.file_stem().expect("ELF must be a file"),
+    };
     let mut tab_path = cli.elf.clone();
     tab_path.set_extension("tab");
-    let protected_size = TBF_HEADER_SIZE.to_string();
     if cli.verbose {
         println!("Package name: {:?}", package_name);
         println!("TAB path: {}", tab_path.display());
-        println!("Protected region size: {}", protected_size);
     }
-    let stack_size = read_stack_size(cli);
     let elf = cli.elf.as_os_str();
     let mut tbf_path = cli.elf.clone();
     tbf_path.set_extension("tbf");
@@ -39,17 +40,11 @@ pub fn convert_elf(cli: &Cli) -> OutFiles {
     let mut command = Command::new("elf2tab");
     #[rustfmt::skip]
     command.args([
-        // TODO: libtock-rs' crates are designed for Tock 2.1's Allow interface,
-        // so we should increment this as soon as the Tock kernel will accept a
-        // 2.1 app.
-        "--kernel-major".as_ref(), "2".as_ref(),
-        "--kernel-minor".as_ref(), "0".as_ref(),
         "-n".as_ref(), package_name,
         "-o".as_ref(), tab_path.as_os_str(),
-        "--protected-region-size".as_ref(), protected_size.as_ref(),
-        "--stack".as_ref(), stack_size.as_ref(),
-        elf,
     ]);
+    command.args(options.args());
+    command.arg(elf);
     if cli.verbose {
         command.arg("-v");
         println!("elf2tab command: {:?}", command);
@@ -88,26 +83,184 @@ pub struct OutFiles {
     pub tbf_path: PathBuf,
 }
 
-// The amount of space to reserve for the TBF header. This must match the
-// TBF_HEADER_SIZE value in the layout file for the platform, which is currently
-// 0x48 for all platforms.
-const TBF_HEADER_SIZE: u32 = 0x48;
-
-// Reads the stack size, and returns it as a String for use on elf2tab's command
-// line.
-fn read_stack_size(cli: &Cli) -> String {
-    let file = elf::File::open_path(&cli.elf).expect("Unable to open ELF");
-    for section in file.sections {
-        // This section name comes from runtime/libtock_layout.ld, and it
-        // matches the size (and location) of the process binary's stack.
-        if section.shdr.name == ".stack" {
-            let stack_size = section.shdr.size.to_string();
-            if cli.verbose {
-                println!("Found .stack section, size: {}", stack_size);
+// Options passed to elf2tab, most of which are read from the ELF file. Numbers
+// are stored as Strings for use on elf2tab's command line.
+struct TbfOptions {
+    stack_size: String,
+    protected_size: String,
+    app_name: Option<String>,
+    minimum_ram_size: Option<String>,
+    kernel_major: String,
+    kernel_minor: String,
+}
+
+impl TbfOptions {
+    // Returns the elf2tab arguments that set these options.
+    fn args(&self) -> Vec<&str> {
+        #[rustfmt::skip]
+        let mut args = vec![
+            "--kernel-major", &self.kernel_major,
+            "--kernel-minor", &self.kernel_minor,
+            "--protected-region-size", &self.protected_size,
+            "--stack", &self.stack_size,
+        ];
+        if let Some(ref minimum_ram_size) = self.minimum_ram_size {
+            args.extend(["--minimum-ram-size", minimum_ram_size]);
+        }
+        args
+    }
+}
+
+// Reads the TBF options from the ELF file.
+fn read_tbf_options(elf: &Path, verbose: bool) -> TbfOptions {
+    let file = elf::File::open_path(elf).expect("Unable to open ELF");
+    let sections = file.sections.iter().map(|section| Section {
+        name: &section.shdr.name,
+        size: section.shdr.size,
+        data: &section.data,
+    });
+    let options = parse_tbf_options(sections).unwrap_or_else(|missing| {
+        panic!(
+            "Unable to find the {} section in {}",
+            missing,
+            elf.display()
+        )
+    });
+    if verbose {
+        println!("Found .stack section, size: {}", options.stack_size);
+        println!("Protected region size: {}", options.protected_size);
+        println!("App name: {:?}", options.app_name);
+        println!("Minimum RAM size: {:?}", options.minimum_ram_size);
+        println!(
+            "Kernel version: {}.{}",
+            options.kernel_major, options.kernel_minor
+        );
+    }
+    options
+}
+
+// The parts of an ELF section parse_tbf_options uses.
+struct Section<'s> {
+    name: &'s str,
+    size: u64,
+    data: &'s [u8],
+}
+
+// Parses the TBF options from the ELF file's sections, returning the name of
+// the first required section that is missing, if any. The section names come
+// from runtime/libtock_layout.ld. The .stack and .tbf_header sections match the
+// size (and location) of the process binary's stack and TBF headers. The other
+// sections are set by libtock_runtime's TBF macros, and may be absent.
+fn parse_tbf_options<'s>(
+    sections: impl IntoIterator<Item = Section<'s>>,
+) -> Result<TbfOptions, &'static str> {
+    let mut stack_size = None;
+    let mut protected_size = None;
+    let mut app_name = None;
+    let mut minimum_ram_size = None;
+    // libtock-rs' crates are designed for Tock 2.1's Allow interface (including
+    // Userspace-Readable Allow), so by default we require a 2.1 kernel.
+    let mut kernel_version = [2, 1];
+    for section in sections {
+        match section.name {
+            ".stack" => stack_size = Some(section.size.to_string()),
+            ".tbf_header" => protected_size = Some(section.size.to_string()),
+            ".tbf_app_name" => {
+                let name = std::str::from_utf8(section.data).expect("Non-UTF-8 app name");
+                app_name = Some(name.to_string());
             }
-            return stack_size;
+            ".tbf_minimum_ram_size" => {
+                minimum_ram_size = Some(read_u32s::<1>(section.data)[0].to_string());
+            }
+            ".tbf_kernel_version" => kernel_version = read_u32s(section.data),
+            _ => {}
         }
     }
+    Ok(TbfOptions {
+        stack_size: stack_size.ok_or(".stack")?,
+        protected_size: protected_size.ok_or(".tbf_header")?,
+        app_name,
+        minimum_ram_size,
+        kernel_major: kernel_version[0].to_string(),
+        kernel_minor: kernel_version[1].to_string(),
+    })
+}
+
+// Parses a section containing N little-endian u32 values (all the targets
+// libtock-rs supports are little-endian).
+fn read_u32s<const N: usize>(data: &[u8]) -> [u32; N] {
+    assert_eq!(data.len(), 4 * N, "TBF option section has the wrong size");
+    let mut values = [0; N];
+    for (value, bytes) in values.iter_mut().zip(data.chunks_exact(4)) {
+        *value = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn section<'s>(name: &'s str, size: u64, data: &'s [u8]) -> Section<'s> {
+        Section { name, size, data }
+    }
+
+    // The sections libtock_runtime's layout always contains.
+    fn required() -> [Section<'static>; 2] {
+        [
+            section(".stack", 0x200, &[]),
+            section(".tbf_header", 0x60, &[]),
+        ]
+    }
 
-    panic!("Unable to find the .stack section in {}", cli.elf.display());
+    #[test]
+    fn defaults() {
+        let options = parse_tbf_options(required()).unwrap();
+        assert_eq!(options.app_name, None);
+        #[rustfmt::skip]
+        assert_eq!(options.args(), [
+            "--kernel-major", "2",
+            "--kernel-minor", "1",
+            "--protected-region-size", "96",
+            "--stack", "512",
+        ]);
+    }
+
+    // The sections libtock2's tbf_options example contains.
+    #[test]
+    fn all_options() {
+        let sections = required().into_iter().chain([
+            section(".text", 0x1000, &[0; 16]),
+            section(".tbf_app_name", 11, b"tbf_options"),
+            section(".tbf_minimum_ram_size", 4, &[0x00, 0x10, 0, 0]),
+            section(".tbf_kernel_version", 8, &[2, 0, 0, 0, 0, 0, 0, 0]),
+        ]);
+        let options = parse_tbf_options(sections).unwrap();
+        assert_eq!(options.app_name.as_deref(), Some("tbf_options"));
+        #[rustfmt::skip]
+        assert_eq!(options.args(), [
+            "--kernel-major", "2",
+            "--kernel-minor", "0",
+            "--protected-region-size", "96",
+            "--stack", "512",
+            "--minimum-ram-size", "4096",
+        ]);
+    }
+
+    #[test]
+    fn missing_sections() {
+        let [stack, tbf_header] = required();
+        assert_eq!(parse_tbf_options([tbf_header]).err(), Some(".stack"));
+        assert_eq!(parse_tbf_options([stack]).err(), Some(".tbf_header"));
+    }
+
+    #[test]
+    #[should_panic(expected = "TBF option section has the wrong size")]
+    fn wrong_size() {
+        let sections =
+            required()
+                .into_iter()
+                .chain([section(".tbf_kernel_version", 4, &[2, 0, 0, 0])]);
+        let _ = parse_tbf_options(sections);
+    }
 }
@@ -1,3 +1,8 @@
+// The board table is shared with libtock_runtime's build script.
+#[allow(dead_code)]
+mod boards {
+    include!("../../runtime/boards.rs");
+}
 mod elf2tab;
 mod output_processor;
 mod qemu;
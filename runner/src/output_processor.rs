@@ -1,5 +1,7 @@
 use super::Cli;
 use libc::{kill, pid_t, SIGINT};
+use libtock_deferred_fmt::decode::{decode_frame, lookup_fmt};
+use libtock_deferred_fmt::FRAME_START;
 use std::io::{stderr, stdin, stdout, BufRead, BufReader, ErrorKind, Stdout, Write};
 use std::process::Child;
 use std::thread::spawn;
@@ -11,6 +13,7 @@ pub fn process(cli: &Cli, mut child: Child) {
     let raw_mode = forward_stdin_if_piped(&mut child);
     forward_stderr_if_piped(&mut child, raw_mode.is_some());
     let mut to_print = Vec::new();
+    let mut converter = OutputConverter::new(FormatStrings::read(cli), raw_mode.is_some());
     let mut reader = BufReader::new(child.stdout.as_mut().expect("Child's stdout not piped."));
     loop {
         let buffer = reader
@@ -20,13 +23,8 @@ pub fn process(cli: &Cli, mut child: Child) {
             // The child process has closed its stdout, likely by exiting.
             break;
         }
-        // Print the bytes received over stdout. If the terminal is in raw mode,
-        // translate '\n' into '\r\n'.
         for &byte in buffer {
-            if raw_mode.is_some() && byte == b'\n' {
-                to_print.push(b'\r');
-            }
-            to_print.push(byte);
+            converter.process_byte(byte, &mut to_print);
         }
         stdout()
             .write_all(&to_print)
@@ -127,3 +125,277 @@ fn forward_stderr_if_piped(child: &mut Child, raw_mode: bool) {
         }
     });
 }
+
+// Converts the bytes received over the child's stdout into the text to print.
+// Deferred formatting frames are decoded once they are complete, rather than
+// being echoed as they arrive. Other bytes are echoed, translating '\n' into
+// "\r\n" if the terminal is in raw mode, and each line is followed by the
+// explanation of any error report it completes.
+struct OutputConverter {
+    format_strings: FormatStrings,
+    diagnostics: Diagnostics,
+    raw_mode: bool,
+    // The current line, or the current frame's data if in_frame is true.
+    line: Vec<u8>,
+    in_frame: bool,
+}
+
+impl OutputConverter {
+    fn new(format_strings: FormatStrings, raw_mode: bool) -> OutputConverter {
+        OutputConverter {
+            format_strings,
+            diagnostics: Diagnostics::default(),
+            raw_mode,
+            line: Vec::new(),
+            in_frame: false,
+        }
+    }
+
+    // Processes a byte of output, appending the text to print to `to_print`.
+    fn process_byte(&mut self, byte: u8, to_print: &mut Vec<u8>) {
+        if byte == FRAME_START as u8 {
+            self.in_frame = true;
+            self.line.clear();
+            return;
+        }
+        if self.in_frame {
+            if byte != b'\n' {
+                self.line.push(byte);
+                return;
+            }
+            self.in_frame = false;
+            let text = self
+                .format_strings
+                .decode(&String::from_utf8_lossy(&self.line));
+            to_print.extend_from_slice(text.as_bytes());
+            self.push_newline(to_print);
+            self.line.clear();
+            return;
+        }
+        if byte != b'\n' {
+            to_print.push(byte);
+            self.line.push(byte);
+            return;
+        }
+        self.push_newline(to_print);
+        if let Some(explanation) = self
+            .diagnostics
+            .process_line(&String::from_utf8_lossy(&self.line))
+        {
+            to_print.extend_from_slice(explanation.as_bytes());
+            self.push_newline(to_print);
+        }
+        self.line.clear();
+    }
+
+    fn push_newline(&self, to_print: &mut Vec<u8>) {
+        if self.raw_mode {
+            to_print.push(b'\r');
+        }
+        to_print.push(b'\n');
+    }
+}
+
+// The contents of the process binary's .libtock_fmt section, which holds the
+// format strings used by libtock_deferred_fmt's dwriteln! macro.
+struct FormatStrings {
+    data: Vec<u8>,
+    address: u64,
+}
+
+impl FormatStrings {
+    // Reads the .libtock_fmt section from the ELF file. The section is absent
+    // if the process binary does not use deferred formatting.
+    fn read(cli: &Cli) -> FormatStrings {
+        let file = elf::File::open_path(&cli.elf).expect("Unable to open ELF");
+        let section = file
+            .sections
+            .into_iter()
+            .find(|section| section.shdr.name == ".libtock_fmt");
+        if cli.verbose {
+            println!(
+                "Deferred format strings: {} bytes",
+                section.as_ref().map_or(0, |section| section.data.len())
+            );
+        }
+        match section {
+            None => FormatStrings {
+                data: Vec::new(),
+                address: 0,
+            },
+            Some(section) => FormatStrings {
+                data: section.data,
+                address: section.shdr.addr,
+            },
+        }
+    }
+
+    // Decodes a deferred formatting frame (without its frame start character
+    // or newline) into the text it represents, or into a description of why
+    // it could not be decoded.
+    fn decode(&self, frame: &str) -> String {
+        decode_frame(frame, |index| lookup_fmt(&self.data, self.address, index))
+            .unwrap_or_else(|error| format!("<deferred formatting error: {}>", error))
+    }
+}
+
+// Recognizes error reports in the Tock system's output, so that they can be
+// explained to the user.
+#[derive(Default)]
+struct Diagnostics {
+    // The app that reported LowLevelDebug alert code 2 (wrong location), if it
+    // has not yet printed the actual and expected addresses of its start code.
+    wrong_location_app: Option<String>,
+}
+
+impl Diagnostics {
+    // Processes a line of output, returning an explanation to print after it,
+    // if the line completes an error report.
+    fn process_line(&mut self, line: &str) -> Option<String> {
+        let (app, message) = parse_low_level_debug(line)?;
+        if message.starts_with("alert code 0x2 ") {
+            self.wrong_location_app = Some(app.to_string());
+            return None;
+        }
+        if self.wrong_location_app.as_deref() != Some(app) {
+            return None;
+        }
+        self.wrong_location_app = None;
+        let (actual, expected) = message.strip_prefix("prints ")?.split_once(' ')?;
+        Some(format!(
+            "Error: the process binary was loaded at {}, but was built to run at \
+             {}. Check that LIBTOCK_PLATFORM matches the board, and that no \
+             other app is installed before this one.",
+            actual, expected
+        ))
+    }
+}
+
+// Splits a LowLevelDebug message (e.g. "LowLevelDebug: App 0x0 prints 0x1")
+// into the app ("0x0") and the message ("prints 0x1").
+fn parse_low_level_debug(line: &str) -> Option<(&str, &str)> {
+    line.trim_end()
+        .strip_prefix("LowLevelDebug: App ")?
+        .split_once(' ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A .libtock_fmt section at address 0x1000, containing "a={}" at 0x1000
+    // and "b={}" at 0x1005.
+    fn format_strings() -> FormatStrings {
+        FormatStrings {
+            data: b"a={}\0b={}\0".to_vec(),
+            address: 0x1000,
+        }
+    }
+
+    // Frames (without the frame start character) for index 0x1000 with the
+    // unsigned argument 5, and index 0x1005 with the signed argument -5.
+    const FRAME_A: &str = "gCAABQ";
+    const FRAME_B: &str = "hSABCQ";
+
+    // Passes `output` through an OutputConverter, returning the text to print.
+    fn convert(output: &str, raw_mode: bool) -> String {
+        let mut converter = OutputConverter::new(format_strings(), raw_mode);
+        let mut to_print = Vec::new();
+        for &byte in output.as_bytes() {
+            converter.process_byte(byte, &mut to_print);
+        }
+        String::from_utf8(to_print).unwrap()
+    }
+
+    #[test]
+    fn decode() {
+        let format_strings = format_strings();
+        assert_eq!(format_strings.decode(FRAME_A), "a=5");
+        assert_eq!(format_strings.decode("hSABCQ\r"), "b=-5");
+        // Index 0xfff precedes the section.
+        assert_eq!(
+            format_strings.decode("/x8ABQ"),
+            "<deferred formatting error: unknown format string 0xfff>"
+        );
+        assert_eq!(
+            FormatStrings {
+                data: Vec::new(),
+                address: 0
+            }
+            .decode(FRAME_A),
+            "<deferred formatting error: unknown format string 0x1000>"
+        );
+    }
+
+    #[test]
+    fn frames() {
+        let frame = |data| format!("{}{}\n", FRAME_START, data);
+        assert_eq!(convert(&frame(FRAME_A), false), "a=5\n");
+        assert_eq!(
+            convert(&(frame(FRAME_A) + &frame(FRAME_B)), true),
+            "a=5\r\nb=-5\r\n"
+        );
+        // Frames may follow partial lines, and are followed by ordinary text.
+        assert_eq!(
+            convert(&format!("ab{}cd\n", frame(FRAME_B)), false),
+            "abb=-5\ncd\n"
+        );
+        assert_eq!(convert("ab\ncd", true), "ab\r\ncd");
+    }
+
+    // A frame does not remain in the line passed to the diagnostics, so a
+    // report split by a frame is still explained.
+    #[test]
+    fn frame_then_diagnostics() {
+        let output = convert(
+            &format!(
+                "LowLevelDebug: App 0x0 alert code 0x2 (wrong location)\n{}{}\n\
+                 LowLevelDebug: App 0x0 prints 0x40000 0x30000\n",
+                FRAME_START, FRAME_A
+            ),
+            false,
+        );
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "a=5");
+        assert!(lines[3].starts_with("Error: the process binary was loaded at 0x40000"));
+    }
+
+    #[test]
+    fn process_line() {
+        let mut diagnostics = Diagnostics::default();
+        assert_eq!(diagnostics.process_line("Hello\n"), None);
+        // Other alert codes do not start a report.
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x1 alert code 0x1 (panic)\n"),
+            None
+        );
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x1 prints 0x40000 0x30000\n"),
+            None
+        );
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x1 alert code 0x2 (wrong location)\n"),
+            None
+        );
+        // Another app's messages do not complete the report.
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x0 prints 0x1 0x2\n"),
+            None
+        );
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x1 prints 0x40000 0x30000\r\n"),
+            Some(
+                "Error: the process binary was loaded at 0x40000, but was built to \
+                 run at 0x30000. Check that LIBTOCK_PLATFORM matches the board, and \
+                 that no other app is installed before this one."
+                    .into()
+            )
+        );
+        // The report has been explained, so it is not explained again.
+        assert_eq!(
+            diagnostics.process_line("LowLevelDebug: App 0x1 prints 0x40000 0x30000\n"),
+            None
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use super::boards::{find_board, Board};
 use super::Cli;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
@@ -8,48 +9,24 @@ use std::process::{Child, Command, Stdio};
 // works with tockloader. If you use it, please report back on how it works so
 // we can fix it or remove this notice!
 pub fn deploy(cli: &Cli, platform: String, tab_path: PathBuf) -> Child {
-    let flags: &[_] = match platform.as_str() {
-        "clue_nrf52840" => &[],
-        "hail" => &[],
-        "microbit_v2" => &["--bundle-apps"],
-        "nrf52" | "nrf52840" => &[
-            "--jlink",
-            "--arch",
-            "cortex-m4",
-            "--board",
-            "nrf52dk",
-            "--jtag-device",
-            "nrf52",
-        ],
+    let tockloader = match find_board(&platform) {
+        Some(Board {
+            tockloader: Some(tockloader),
+            ..
+        }) => tockloader,
         _ => panic!("Cannot deploy to platform {} via tockloader", platform),
     };
+    let flags = tockloader.flags;
     if cli.verbose {
         println!("Tockloader flags: {:?}", flags);
     }
 
     // Tockloader listen's ability to receive every message from the Tock system
-    // varies from platform to platform. We look up the platform, and if it is
-    // not satisfactorily reliable we output a warning for the user.
-    let reliable_listen = match platform.as_str() {
-        // tockloader listen will reset the Hail, allowing it to capture all
-        // printed messages.
-        "hail" => true,
-
-        // Microbit uses CDC over USB, which buffers messages so that tockloader
-        // listen can receive messages sent before it was started. As long as
-        // tockloader listen launches before the timeout, there will not be
-        // dropped messages. This is good enough for our purposes.
-        "microbit_v2" => true,
-
-        // tockloader listen doesn't reset the nrf52, and there's no message
-        // queueing mechanism. Therefore, tockloader listen will likely miss
-        // messages printed quickly after the process binary is deployed.
-        "nrf52" | "nrf52840" => false,
-
-        // We shouldn't hit this case, because the flag determination code above
-        // should error out on unknown platforms.
-        _ => panic!("Unknown reliability for {}", platform),
-    };
+    // varies from platform to platform (see boards.rs). If it is not
+    // satisfactorily reliable we output a warning for the user.
+    let reliable_listen = tockloader
+        .reliable_listen
+        .unwrap_or_else(|| panic!("Unknown reliability for {}", platform));
     if !reliable_listen {
         println!(
             "Warning: tockloader listen may miss early messages on platform {}",
@@ -0,0 +1,302 @@
+// The boards libtock-rs knows how to build process binaries for. This file is
+// the single source of truth for board-specific configuration: it is included
+// by libtock_runtime's build script, which generates the process binary's
+// linker layout from it, and by the runner, which uses the deploy hints.
+//
+// To add a board, add an entry to BOARDS.
+
+/// A board that process binaries can be built for, selected by setting
+/// `LIBTOCK_PLATFORM` to the board's name.
+pub struct Board {
+    pub name: &'static str,
+    pub description: &'static str,
+
+    /// The preferred target triple for the board. The process binary may be
+    /// built for any target with the same architecture.
+    pub target: &'static str,
+
+    /// The (origin, length) of the area in flash the process binary occupies,
+    /// including its TBF headers.
+    pub flash: (u32, u32),
+
+    /// The (origin, length) of the RAM the process has access to.
+    pub ram: (u32, u32),
+
+    /// The default size of the TBF headers (see the `tbf_header_size!` macro).
+    pub tbf_header_size: u32,
+
+    /// How to deploy to the board in QEMU, if supported.
+    pub qemu: Option<Qemu>,
+
+    /// How to deploy to the board using tockloader, if supported.
+    pub tockloader: Option<Tockloader>,
+}
+
+/// QEMU deploy hints. The process binary is loaded at the start of the board's
+/// flash region.
+pub struct Qemu {
+    /// Board-specific arguments to pass to QEMU.
+    pub args: &'static [&'static str],
+}
+
+/// tockloader deploy hints.
+pub struct Tockloader {
+    /// Board-specific flags to pass to tockloader.
+    pub flags: &'static [&'static str],
+
+    /// Whether `tockloader listen` reliably receives every message from the
+    /// Tock system, including those printed immediately after the process
+    /// binary is deployed. `None` if this has not been evaluated, in which case
+    /// the runner refuses to deploy to the board.
+    pub reliable_listen: Option<bool>,
+}
+
+const K: u32 = 1024;
+const M: u32 = 1024 * 1024;
+
+// The TBF header size used by all of the boards in this table.
+const TBF_HEADER_SIZE: u32 = 0x48;
+
+pub const BOARDS: &[Board] = &[
+    Board {
+        name: "apollo3",
+        description: "Apollo3 MCU",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0004_0000, 0x0006_0000),
+        ram: (0x1000_2000, 0x2000),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "clue_nrf52840",
+        description: "Adafruit CLUE",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0008_0000, 512 * K),
+        ram: (0x2000_6000, 216 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: Some(Tockloader {
+            flags: &[],
+            // Not yet evaluated.
+            reliable_listen: None,
+        }),
+    },
+    Board {
+        name: "esp32-c3-devkitM-1",
+        description: "ESP32-C3-DevKitM-1",
+        target: "riscv32imc-unknown-none-elf",
+        flash: (0x403B_0000, 0x3_0000),
+        // Note that the SRAM address may need to be changed depending on the
+        // kernel binary, check for the actual address of APP_MEMORY!
+        ram: (0x3FCA_1800, 0x2_E800),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "hail",
+        description: "Hail",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0003_0000, 0x0004_0000),
+        ram: (0x2000_8000, 62 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: Some(Tockloader {
+            flags: &[],
+            // tockloader listen will reset the Hail, allowing it to capture
+            // all printed messages.
+            reliable_listen: Some(true),
+        }),
+    },
+    Board {
+        name: "hifive1",
+        description: "SiFive HiFive1 rev B",
+        target: "riscv32imac-unknown-none-elf",
+        flash: (0x2004_0000, 32 * M),
+        // Note that the SRAM address may need to be changed depending on the
+        // kernel binary, check for the actual address of APP_MEMORY!
+        ram: (0x8000_2800, 0x1800),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: Some(Qemu {
+            #[rustfmt::skip]
+            args: &[
+                "-kernel", "tock/target/riscv32imac-unknown-none-elf/release/hifive1",
+                "-M", "sifive_e,revb=true",
+            ],
+        }),
+        tockloader: None,
+    },
+    Board {
+        name: "imix",
+        description: "Imix",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0004_0000, 0x0004_0000),
+        ram: (0x2000_8000, 62 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "imxrt1050",
+        description: "i.MX RT1050 EVKB",
+        target: "thumbv7em-none-eabi",
+        flash: (0x6300_2000, 0x0100_0000),
+        ram: (0x2000_4000, 112 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "microbit_v2",
+        description: "BBC micro:bit v2",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0004_0000, 256 * K),
+        ram: (0x2000_4000, 112 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: Some(Tockloader {
+            flags: &["--bundle-apps"],
+            // Microbit uses CDC over USB, which buffers messages so that
+            // tockloader listen can receive messages sent before it was
+            // started. As long as tockloader listen launches before the
+            // timeout, there will not be dropped messages. This is good enough
+            // for our purposes.
+            reliable_listen: Some(true),
+        }),
+    },
+    Board {
+        name: "msp432",
+        description: "MSP432P401R LaunchPad",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0002_0000, 0x0002_0000),
+        ram: (0x2000_4000, 0x2000),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "nano_rp2040_connect",
+        description: "Arduino Nano RP2040 Connect",
+        target: "thumbv6m-none-eabi",
+        flash: (0x1002_0000, 256 * K),
+        ram: (0x2000_4000, 248 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "nrf52",
+        description: "nRF52-DK",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0003_0000, 0x0006_0000),
+        ram: (0x2000_4000, 62 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: Some(NRF52_TOCKLOADER),
+    },
+    Board {
+        name: "nrf52840",
+        description: "nRF52840-DK",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0003_0000, 0x000D_0000),
+        ram: (0x2000_4000, 62 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: Some(NRF52_TOCKLOADER),
+    },
+    Board {
+        name: "nucleo_f429zi",
+        description: "Nucleo-F429ZI",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0804_0000, 255 * K),
+        ram: (0x2000_4000, 112 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "nucleo_f446re",
+        description: "Nucleo-F446RE",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0804_0000, 255 * K),
+        ram: (0x2000_4000, 176 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "opentitan",
+        description: "OpenTitan Earl Grey on the CW310 FPGA",
+        target: "riscv32imc-unknown-none-elf",
+        flash: (0x2003_0000, 32 * M),
+        // Note that the SRAM address may need to be changed depending on the
+        // kernel binary, check for the actual address of APP_MEMORY!
+        ram: (0x1000_4000, 512 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: Some(Qemu {
+            #[rustfmt::skip]
+            args: &[
+                "-bios", "tock/tools/qemu-runner/opentitan-boot-rom.elf",
+                "-kernel", "tock/target/riscv32imc-unknown-none-elf/release/earlgrey-cw310",
+                "-M", "opentitan",
+            ],
+        }),
+        tockloader: None,
+    },
+    Board {
+        name: "pic",
+        description: "Position-independent process binaries",
+        target: "thumbv7em-none-eabi",
+        // Requires libtock_runtime's `pic` feature. These are the link
+        // addresses libtock-c uses for its position-independent apps, which
+        // elf2tab is expected to treat as position-independent (i.e. not add
+        // fixed-address TBF headers); this has not been tested with
+        // libtock-rs process binaries. The lengths are upper bounds; the
+        // kernel verifies the process fits in the memory it is given.
+        flash: (0x8000_0000, 0x1000_0000),
+        ram: (0x0000_0000, 0x1000_0000),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "raspberrypi_pico",
+        description: "Raspberry Pi Pico",
+        target: "thumbv6m-none-eabi",
+        flash: (0x1002_0000, 256 * K),
+        ram: (0x2000_4000, 248 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+    Board {
+        name: "stm32f3discovery",
+        description: "STM32F3DISCOVERY",
+        target: "thumbv7em-none-eabi",
+        flash: (0x0802_0000, 0x0002_0000),
+        ram: (0x2000_4000, 48 * K),
+        tbf_header_size: TBF_HEADER_SIZE,
+        qemu: None,
+        tockloader: None,
+    },
+];
+
+#[rustfmt::skip]
+const NRF52_TOCKLOADER: Tockloader = Tockloader {
+    flags: &[
+        "--jlink",
+        "--arch", "cortex-m4",
+        "--board", "nrf52dk",
+        "--jtag-device", "nrf52",
+    ],
+    // tockloader listen doesn't reset the nrf52, and there's no message
+    // queueing mechanism. Therefore, tockloader listen will likely miss
+    // messages printed quickly after the process binary is deployed.
+    reliable_listen: Some(false),
+};
+
+/// Returns the board with the given name, if it is in BOARDS.
+pub fn find_board(name: &str) -> Option<&'static Board> {
+    BOARDS.iter().find(|board| board.name == name)
+}
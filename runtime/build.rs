@@ -1,10 +1,19 @@
-// auto_layout() identifies the correct linker scripts to use based on the
-// LIBTOCK_PLATFORM environment variable, and copies the linker scripts into
-// OUT_DIR. The cargo invocation must pass -C link-arg=-Tlayout.ld to rustc
+#[cfg(not(feature = "no_auto_layout"))]
+#[allow(dead_code)]
+mod boards {
+    include!("boards.rs");
+}
+
+// auto_layout() generates the linker script for the platform selected by the
+// LIBTOCK_PLATFORM environment variable, and copies the generic linker script
+// into OUT_DIR. The cargo invocation must pass -C link-arg=-Tlayout.ld to rustc
 // (using the rustflags cargo config).
+//
+// LIBTOCK_PLATFORM is either the name of a board in boards.rs or an explicit
+// memory layout specification (see parse_spec).
 #[cfg(not(feature = "no_auto_layout"))]
 fn auto_layout() {
-    use std::fs::copy;
+    use std::fs::{copy, write};
     use std::path::PathBuf;
 
     const PLATFORM_CFG_VAR: &str = "LIBTOCK_PLATFORM";
@@ -13,6 +22,7 @@ fn auto_layout() {
     // Note: we need to print these rerun-if commands before using the variable
     // or file, so that if the build script fails cargo knows when to re-run it.
     println!("cargo:rerun-if-env-changed={}", PLATFORM_CFG_VAR);
+    println!("cargo:rerun-if-changed=boards.rs");
 
     // Read configuration from environment variables.
 
@@ -30,13 +40,43 @@ fn auto_layout() {
     // should all be valid UTF-8).
     let platform = std::env::var(PLATFORM_CFG_VAR).expect("Please specify LIBTOCK_PLATFORM");
 
-    // Copy the platform-specific layout file into OUT_DIR.
-    let platform_filename = format!("{}.ld", platform);
-    let platform_path: PathBuf = ["layouts", &platform_filename].iter().collect();
-    println!("cargo:rerun-if-changed={}", platform_path.display());
-    assert!(platform_path.exists(), "Unknown platform {}", platform);
+    let layout = if platform.contains('=') {
+        parse_spec(&platform)
+    } else {
+        let board = boards::find_board(&platform).unwrap_or_else(|| {
+            let names: Vec<_> = boards::BOARDS.iter().map(|board| board.name).collect();
+            panic!(
+                "Unknown platform {}. Supported platforms: {}. Alternatively, specify the \
+                 memory layout as LIBTOCK_PLATFORM=flash=0x..,ram=0x..",
+                platform,
+                names.join(", ")
+            )
+        });
+        check_arch(board);
+        Layout {
+            description: format!("{} ({})", board.description, board.name),
+            flash: board.flash,
+            ram: board.ram,
+            tbf_header_size: board.tbf_header_size,
+        }
+    };
+
+    // Generate the platform-specific layout file in OUT_DIR.
     let out_platform_path: PathBuf = [out_dir, "layout.ld"].iter().collect();
-    copy(&platform_path, out_platform_path).expect("Unable to copy platform layout into OUT_DIR");
+    write(out_platform_path, layout.linker_script())
+        .expect("Unable to write platform layout into OUT_DIR");
+
+    // Generate the position-independent rt_header fields, which
+    // libtock_layout.ld includes. They are only emitted when the `pic` feature
+    // is enabled, so that statically-linked process binaries do not pay for
+    // them.
+    let pic = std::env::var_os("CARGO_FEATURE_PIC").is_some();
+    assert!(
+        !pic || platform == "pic",
+        "libtock_runtime's pic feature requires LIBTOCK_PLATFORM=pic"
+    );
+    let out_pic_path: PathBuf = [out_dir, "libtock_rt_header_pic.ld"].iter().collect();
+    write(out_pic_path, rt_header_pic(pic)).expect("Unable to write rt_header fields into OUT_DIR");
 
     // Copy the generic layout file into OUT_DIR.
     let out_layout_generic: PathBuf = [out_dir, LAYOUT_GENERIC_FILENAME].iter().collect();
@@ -48,6 +88,149 @@ fn auto_layout() {
     println!("cargo:rustc-link-search={}", out_dir);
 }
 
+// The memory layout of a process binary.
+#[cfg(not(feature = "no_auto_layout"))]
+struct Layout {
+    description: String,
+    flash: (u32, u32),
+    ram: (u32, u32),
+    tbf_header_size: u32,
+}
+
+#[cfg(not(feature = "no_auto_layout"))]
+impl Layout {
+    fn linker_script(&self) -> String {
+        format!(
+            "/* Layout for {}, generated by libtock_runtime's build script. */\n\
+             \n\
+             MEMORY {{\n  \
+               FLASH (X) : ORIGIN = {:#010x}, LENGTH = {:#010x}\n  \
+               RAM   (W) : ORIGIN = {:#010x}, LENGTH = {:#010x}\n\
+             }}\n\
+             \n\
+             TBF_HEADER_SIZE = {:#x};\n\
+             INCLUDE libtock_layout.ld\n",
+            self.description,
+            self.flash.0,
+            self.flash.1,
+            self.ram.0,
+            self.ram.1,
+            self.tbf_header_size
+        )
+    }
+}
+
+// Returns the contents of libtock_rt_header_pic.ld, which contains the
+// rt_header fields used by the position-independent startup code (see
+// runtime/src/startup/asm_arm_pic.s).
+#[cfg(not(feature = "no_auto_layout"))]
+fn rt_header_pic(pic: bool) -> &'static str {
+    match pic {
+        false => {
+            "/* The pic feature is disabled, so rt_header has no position-independent \
+                  fields. */\n"
+        }
+        true => {
+            "/* rt_header fields for position-independent process binaries, generated \
+                 by libtock_runtime's build script. */\n\
+                 LONG(rt_header);\n\
+                 LONG(ADDR(.rel.dyn));\n\
+                 LONG(SIZEOF(.rel.dyn));\n"
+        }
+    }
+}
+
+// Parses an explicit memory layout, for boards that are not in boards.rs. The
+// specification is a comma-separated list of key=value pairs, for example:
+//     flash=0x40000,ram=0x20008000,flash_length=0x40000
+// flash and ram (the origins) are required; flash_length, ram_length, and
+// tbf_header_size are optional.
+#[cfg(not(feature = "no_auto_layout"))]
+fn parse_spec(spec: &str) -> Layout {
+    const DEFAULT_FLASH_LENGTH: u32 = 0x10_0000;
+    const DEFAULT_RAM_LENGTH: u32 = 0x1_0000;
+    const DEFAULT_TBF_HEADER_SIZE: u32 = 0x48;
+
+    let (mut flash, mut ram) = (None, None);
+    let mut flash_length = DEFAULT_FLASH_LENGTH;
+    let mut ram_length = DEFAULT_RAM_LENGTH;
+    let mut tbf_header_size = DEFAULT_TBF_HEADER_SIZE;
+    for entry in spec.split(',') {
+        let (key, value) = entry
+            .split_once('=')
+            .unwrap_or_else(|| panic!("Invalid LIBTOCK_PLATFORM entry {:?}", entry));
+        let value = parse_u32(value.trim())
+            .unwrap_or_else(|| panic!("Invalid LIBTOCK_PLATFORM value {:?}", entry));
+        match key.trim() {
+            "flash" => flash = Some(value),
+            "ram" => ram = Some(value),
+            "flash_length" => flash_length = value,
+            "ram_length" => ram_length = value,
+            "tbf_header_size" => tbf_header_size = value,
+            key => panic!(
+                "Unknown LIBTOCK_PLATFORM key {:?}. Supported keys: flash, ram, \
+                 flash_length, ram_length, tbf_header_size",
+                key
+            ),
+        }
+    }
+    Layout {
+        description: format!("LIBTOCK_PLATFORM={}", spec),
+        flash: (
+            flash.expect("LIBTOCK_PLATFORM specification is missing flash"),
+            flash_length,
+        ),
+        ram: (
+            ram.expect("LIBTOCK_PLATFORM specification is missing ram"),
+            ram_length,
+        ),
+        tbf_header_size,
+    }
+}
+
+// Parses a decimal or 0x-prefixed hexadecimal number, optionally followed by a
+// K or M suffix (as in linker scripts).
+#[cfg(not(feature = "no_auto_layout"))]
+fn parse_u32(value: &str) -> Option<u32> {
+    let value = value.replace('_', "");
+    let (value, multiplier) = match value.as_bytes().last() {
+        Some(b'K' | b'k') => (&value[..value.len() - 1], 1024),
+        Some(b'M' | b'm') => (&value[..value.len() - 1], 1024 * 1024),
+        _ => (&value[..], 1),
+    };
+    let number = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => value.parse().ok()?,
+    };
+    number.checked_mul(multiplier)
+}
+
+// Verifies the board's architecture matches the architecture being built for.
+#[cfg(not(feature = "no_auto_layout"))]
+fn check_arch(board: &boards::Board) {
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").expect("Unable to read target arch");
+    // Only check targets libtock_runtime supports, so that host builds (e.g.
+    // `cargo doc`) are not affected.
+    if target_arch != "arm" && target_arch != "riscv32" {
+        return;
+    }
+    let board_arch = match board.target {
+        target if target.starts_with("thumb") || target.starts_with("arm") => "arm",
+        target if target.starts_with("riscv32") => "riscv32",
+        target => target,
+    };
+    assert!(
+        board_arch == target_arch,
+        "Board {} is not supported on {} (use target {})",
+        board.name,
+        target_arch,
+        board.target
+    );
+}
+
 fn main() {
     #[cfg(not(feature = "no_auto_layout"))]
     auto_layout();
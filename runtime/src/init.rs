@@ -0,0 +1,80 @@
+//! Functions that run before the process binary's `main`. Libraries that need
+//! setup (such as allocators, loggers, or panic handlers) can register an
+//! initialization function with `init_fn!` rather than requiring every process
+//! binary to call it.
+
+/// Registers a function with the signature `fn()` to run before the process
+/// binary's `main`. Functions run in increasing priority order; functions with
+/// the same priority run in an unspecified order. The priority is an integer
+/// literal between 0 and 65535, and defaults to 1000. libtock-rs uses the
+/// following priorities:
+///
+/// | Priority | Used for                  |
+/// | -------- | ------------------------- |
+/// | 100      | Memory allocators         |
+/// | 200      | Logging and console setup |
+/// | 1000     | Default                   |
+///
+/// # Example
+/// ```ignore
+/// fn init_logger() { /* Omitted */ }
+/// libtock_runtime::init_fn!{init_logger, priority = 200}
+///
+/// fn init_app() { /* Omitted */ }
+/// libtock_runtime::init_fn!{init_app}
+/// ```
+// init_fn places a pointer to the function in a .libtock_init.$priority
+// section. The linker script sorts those sections by priority and places them
+// between _libtock_init_start and _libtock_init_end. The pointers are in .data
+// (rather than flash) so that position-independent process binaries can
+// relocate them.
+#[macro_export]
+macro_rules! init_fn {
+    {$name:path} => {
+        $crate::init_fn!{$name, priority = 1000}
+    };
+    {$name:path, priority = $priority:literal} => {
+        const _: () = {
+            // Verifies the priority is in range.
+            const _: u16 = $priority;
+            #[link_section = concat!(".libtock_init.", $priority)]
+            #[used]
+            static INIT_FN: fn() = $name;
+        };
+    };
+}
+
+// Runs the functions registered with init_fn!, in priority order. Called by
+// rust_start.
+#[cfg(not(test))]
+pub(crate) fn run() {
+    extern "Rust" {
+        // Defined by libtock_layout.ld.
+        static _libtock_init_start: fn();
+        static _libtock_init_end: fn();
+    }
+    // The bounds of the init_fn! pointers. Code in position-independent process
+    // binaries cannot refer to linker-defined symbols directly, so their
+    // addresses are stored in .data (hence static mut), where they are
+    // relocated like other pointers.
+    // Safety: Only the addresses of the linker-defined symbols are used.
+    static mut BOUNDS: [*const fn(); 2] = unsafe {
+        [
+            core::ptr::addr_of!(_libtock_init_start),
+            core::ptr::addr_of!(_libtock_init_end),
+        ]
+    };
+    // The read is volatile so that the compiler does not replace it with the
+    // addresses themselves.
+    // Safety: BOUNDS is never written.
+    let [mut init_fn, end] = unsafe { core::ptr::read_volatile(core::ptr::addr_of!(BOUNDS)) };
+    while init_fn < end {
+        // Safety: The linker script places the function pointers registered by
+        // init_fn! between _libtock_init_start and _libtock_init_end, and .data
+        // has been initialized.
+        unsafe {
+            (*init_fn)();
+            init_fn = init_fn.add(1);
+        }
+    }
+}
@@ -4,9 +4,11 @@
 //! `libtock_runtime` is designed for statically-compiled binaries, and needs to
 //! know the location (in non-volatile memory and RAM) at which the process will
 //! execute. It reads the `LIBTOCK_PLATFORM` variable to determine what location
-//! to build for (see the `layouts/` directory to see what platforms are
-//! available). It expects the following cargo config options to be set (e.g. in
-//! `.cargo/config`):
+//! to build for. `LIBTOCK_PLATFORM` is either the name of a board listed in
+//! `boards.rs`, or an explicit memory layout such as
+//! `flash=0x40000,ram=0x20008000` (which may also specify `flash_length`,
+//! `ram_length`, and `tbf_header_size`). It expects the following cargo config
+//! options to be set (e.g. in `.cargo/config`):
 //! ```
 //! [build]
 //! rustflags = [
@@ -17,11 +19,51 @@
 //! If a process binary wants to support another platform, it can set the
 //! `no_auto_layout` feature on `libtock_runtime` to disable this functionality
 //! and provide its own layout file.
+//!
+//! On ARM, the `pic` feature builds position-independent process binaries
+//! instead, which can be loaded at any location. These must be built with
+//! `LIBTOCK_PLATFORM=pic`, and with the following rustflags (rather than the
+//! above):
+//! ```
+//! rustflags = [
+//!     "-C", "relocation-model=ropi-rwpi",
+//!     "-C", "link-arg=-Tlayout.ld",
+//!     "-C", "link-arg=--pie",
+//!     "-C", "link-arg=-znorelro",
+//! ]
+//! ```
+//! `core` must also be compiled with those flags, which requires
+//! `-Z build-std=core` (`make pic` does this). Flash cannot be relocated at
+//! runtime, so position-independent process binaries cannot contain code
+//! pointers in read-only data, such as trait object vtables; the linker script
+//! rejects binaries that do. RISC-V is not supported, because LLVM does not
+//! implement an equivalent of ARM's ROPI/RWPI relocation models for RISC-V.
 
 #![no_std]
 #![warn(unsafe_op_in_unsafe_fn)]
 
+mod init;
+mod memory_hints;
+#[cfg(test)]
+mod memory_hints_tests;
+#[cfg(any(feature = "pic", test))]
+mod relocation;
+#[cfg(test)]
+mod relocation_tests;
+#[cfg(feature = "stack_paint")]
+pub mod stack;
+// The startup code makes system calls, so it is not built for unit tests (which
+// run on the host).
+#[cfg(not(test))]
 mod startup;
+#[cfg(not(test))]
+#[doc(hidden)]
+pub use startup::exit_hook;
+#[cfg(not(test))]
+pub use startup::{RELOCATION_FAILED_EXIT_CODE, WRONG_LOCATION_EXIT_CODE};
+mod tbf;
+#[doc(hidden)]
+pub use tbf::__str_to_array;
 
 /// TockSyscalls implements `libtock_platform::Syscalls`.
 pub struct TockSyscalls;
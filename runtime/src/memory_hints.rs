@@ -0,0 +1,18 @@
+//! Tells the kernel where the process' stack and heap are, so that it can
+//! display a useful memory layout if the process faults. This is separate from
+//! the startup code so that it can be unit tested on the host.
+
+use libtock_platform::Syscalls;
+
+/// Passes the stack top and initial process break to the kernel's Memop debug
+/// hints. `stack_top` and `initial_break` are the values from rt_header, which
+/// start also uses to set up the stack and process break. In position-
+/// independent process binaries they are offsets from the start of RAM, and
+/// `ram_start` is the runtime address of RAM; otherwise `ram_start` is 0.
+///
+/// The hints only affect debugging output, so failures (e.g. from kernels that
+/// do not support them) are ignored.
+pub(crate) fn report<S: Syscalls>(stack_top: usize, initial_break: usize, ram_start: usize) {
+    let _ = S::memop_debug_stack_start(ram_start.wrapping_add(stack_top) as *const u8);
+    let _ = S::memop_debug_heap_start(ram_start.wrapping_add(initial_break) as *const u8);
+}
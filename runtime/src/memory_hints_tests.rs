@@ -0,0 +1,68 @@
+use crate::memory_hints::report;
+use libtock_platform::{memop_id, ErrorCode};
+use libtock_unittest::{fake, ExpectedSyscall, SyscallLogEntry};
+
+#[test]
+fn absolute() {
+    let kernel = fake::Kernel::new();
+    report::<fake::Syscalls>(0x2000_0800, 0x2000_0900, 0);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 0x2000_0800,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_HEAP_START,
+                argument0: 0x2000_0900,
+            },
+        ]
+    );
+}
+
+#[test]
+fn position_independent() {
+    // rt_header holds offsets from the start of RAM, which the kernel chose at
+    // load time.
+    let kernel = fake::Kernel::new();
+    report::<fake::Syscalls>(0x800, 0x900, 0x2000_4000);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 0x2000_4800,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_HEAP_START,
+                argument0: 0x2000_4900,
+            },
+        ]
+    );
+}
+
+#[test]
+fn unsupported() {
+    // A kernel that rejects the stack hint still receives the heap hint.
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::DEBUG_STACK_START,
+        argument0: 0x2000_0800,
+        return_error: Some(ErrorCode::NoSupport),
+    });
+    report::<fake::Syscalls>(0x2000_0800, 0x2000_0900, 0);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 0x2000_0800,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_HEAP_START,
+                argument0: 0x2000_0900,
+            },
+        ]
+    );
+}
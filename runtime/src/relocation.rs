@@ -0,0 +1,63 @@
+//! Applies the dynamic relocations of position-independent process binaries.
+//! This is separate from the startup code so that it can be unit tested on the
+//! host.
+//!
+//! Position-independent process binaries are linked with FLASH at 0x80000000
+//! and RAM at 0 (see the `pic` entry in `boards.rs`), and with `--pie`, which
+//! makes the linker emit an `R_ARM_RELATIVE` relocation for each pointer stored
+//! in `.data`.
+//! Because flash and RAM are moved independently, the target of each pointer is
+//! identified by its link-time address: addresses at or above 0x80000000 point
+//! into flash, and all other addresses point into RAM.
+
+// The link-time address of FLASH, from the `pic` entry in boards.rs.
+pub(crate) const FLASH_ORIGIN: usize = 0x8000_0000;
+
+pub(crate) const R_ARM_RELATIVE: usize = 23;
+
+// An ELF relocation without an addend (Elf32_Rel).
+#[repr(C)]
+pub(crate) struct Rel {
+    pub offset: usize,
+    pub info: usize,
+}
+
+// Returned by apply if a relocation cannot be applied by the runtime.
+#[derive(Debug, PartialEq)]
+pub(crate) struct UnsupportedRelocation;
+
+/// Applies `relocations` to `.data`, which has been copied into RAM at
+/// `ram_start`. `flash_offset` is the difference between the runtime and
+/// link-time addresses of flash.
+///
+/// # Safety
+/// Every `R_ARM_RELATIVE` relocation in `relocations` with an offset below
+/// `FLASH_ORIGIN` must identify a word of `.data` (relative to `ram_start`)
+/// that is valid to read and write. Must be called before any code accesses
+/// `.data`.
+pub(crate) unsafe fn apply(
+    relocations: &[Rel],
+    ram_start: usize,
+    flash_offset: usize,
+) -> Result<(), UnsupportedRelocation> {
+    for relocation in relocations {
+        // The runtime can only patch RAM, and .data only contains
+        // R_ARM_RELATIVE relocations unless the process binary was linked
+        // incorrectly (e.g. without the ropi-rwpi relocation model).
+        if relocation.info & 0xff != R_ARM_RELATIVE || relocation.offset >= FLASH_ORIGIN {
+            return Err(UnsupportedRelocation);
+        }
+        let location = ram_start.wrapping_add(relocation.offset) as *mut usize;
+        // Safety: The caller guarantees the relocation identifies a word of
+        // .data.
+        unsafe {
+            let value = location.read();
+            if value >= FLASH_ORIGIN {
+                location.write(value.wrapping_add(flash_offset));
+            } else {
+                location.write(value.wrapping_add(ram_start));
+            }
+        }
+    }
+    Ok(())
+}
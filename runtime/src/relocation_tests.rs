@@ -0,0 +1,50 @@
+use crate::relocation::{apply, Rel, UnsupportedRelocation, FLASH_ORIGIN, R_ARM_RELATIVE};
+use core::mem::size_of;
+
+// Returns an R_ARM_RELATIVE relocation for the word at index `word` of .data.
+fn relative(word: usize) -> Rel {
+    Rel {
+        offset: word * size_of::<usize>(),
+        info: R_ARM_RELATIVE,
+    }
+}
+
+#[test]
+fn relocate() {
+    // A synthetic .data, linked at RAM address 0. It contains a pointer into
+    // flash, a pointer into RAM, and a word that is not relocated.
+    let mut data = [FLASH_ORIGIN + 0x100, 0x8, 0x8];
+    let ram_start = data.as_mut_ptr() as usize;
+    let relocations = [relative(0), relative(1)];
+    // Safety: The relocations identify words of data.
+    assert_eq!(unsafe { apply(&relocations, ram_start, 0x1000) }, Ok(()));
+    assert_eq!(data, [FLASH_ORIGIN + 0x1100, ram_start + 0x8, 0x8]);
+
+    // An empty .rel.dyn (e.g. a process binary with no pointers in .data) is
+    // valid.
+    assert_eq!(unsafe { apply(&[], ram_start, 0x1000) }, Ok(()));
+}
+
+#[test]
+fn unsupported() {
+    let mut data = [0x8];
+    let ram_start = data.as_mut_ptr() as usize;
+    // A relocation of a type other than R_ARM_RELATIVE.
+    let relocations = [Rel { offset: 0, info: 2 }];
+    // Safety: The relocation identifies a word of data.
+    assert_eq!(
+        unsafe { apply(&relocations, ram_start, 0x1000) },
+        Err(UnsupportedRelocation)
+    );
+    // A relocation in flash, which the runtime cannot modify.
+    let relocations = [Rel {
+        offset: FLASH_ORIGIN,
+        info: R_ARM_RELATIVE,
+    }];
+    // Safety: apply does not access locations in flash.
+    assert_eq!(
+        unsafe { apply(&relocations, ram_start, 0x1000) },
+        Err(UnsupportedRelocation)
+    );
+    assert_eq!(data, [0x8]);
+}
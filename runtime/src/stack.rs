@@ -0,0 +1,99 @@
+//! Stack usage measurement. When the `stack_paint` feature is enabled,
+//! `rust_start` fills the unused part of the stack with a known pattern before
+//! calling `main`. The deepest point the stack has reached can then be found by
+//! looking for the lowest address whose pattern has been overwritten.
+//!
+//! When `main` returns, the stack size and high-water mark are printed through
+//! `LowLevelDebug`, which can be used to tune the process binary's
+//! `stack_size!` value. They are not printed if the process exits another way
+//! (e.g. by calling `exit_terminate` or `exit_restart` directly) or never exits;
+//! such processes can call `report` themselves.
+
+use libtock_low_level_debug::LowLevelDebug;
+
+// The value the stack is painted with.
+const PAINT: u32 = 0xcdcd_cdcd;
+
+extern "C" {
+    // Defined by libtock_layout.ld.
+    static _stack_bottom: u8;
+    static _stack_top: u8;
+}
+
+// Returns the (word-aligned) bottom and the top of the stack.
+fn bounds() -> (usize, usize) {
+    // Safety: Only the addresses of the linker-defined symbols are used.
+    let (bottom, top) = unsafe {
+        (
+            core::ptr::addr_of!(_stack_bottom) as usize,
+            core::ptr::addr_of!(_stack_top) as usize,
+        )
+    };
+    ((bottom + 3) & !3, top)
+}
+
+/// Returns the size of the stack in bytes.
+pub fn size() -> usize {
+    let (bottom, top) = bounds();
+    top - bottom
+}
+
+/// Returns the largest number of bytes of stack that have been in use at once
+/// since the process started. The measurement cannot distinguish stack memory
+/// that was written with the paint value from unused stack memory, so it may
+/// underestimate usage slightly.
+pub fn high_water_mark() -> usize {
+    let (bottom, top) = bounds();
+    let mut address = bottom;
+    // Safety: The stack is valid for reads from bottom to top.
+    while address < top && unsafe { core::ptr::read_volatile(address as *const u32) } == PAINT {
+        address += 4;
+    }
+    top - address
+}
+
+// Fills the stack between its bottom and the current stack pointer with PAINT.
+pub(crate) fn paint() {
+    let (bottom, _) = bounds();
+    // Safety: The stack memory below the stack pointer is unused, and the loop
+    // stops at the stack pointer.
+    #[cfg(target_arch = "arm")]
+    unsafe {
+        core::arch::asm!(
+            // Uses fixed low registers, as Thumb-1 cannot use high registers
+            // with most of these instructions.
+            "2:",
+            "mov r2, sp",
+            "cmp r0, r2",
+            "bhs 3f",
+            "str r1, [r0]",
+            "adds r0, #4",
+            "b 2b",
+            "3:",
+            inout("r0") bottom => _, // address
+            in("r1") PAINT,
+            out("r2") _, // stack pointer
+            options(nostack),
+        );
+    }
+    #[cfg(target_arch = "riscv32")]
+    unsafe {
+        core::arch::asm!(
+            "2:",
+            "bgeu {address}, sp, 3f",
+            "sw {paint}, 0({address})",
+            "addi {address}, {address}, 4",
+            "j 2b",
+            "3:",
+            address = inout(reg) bottom => _,
+            paint = in(reg) PAINT,
+            options(nostack),
+        );
+    }
+}
+
+/// Prints the stack size and high-water mark through `LowLevelDebug`, as two
+/// numbers. Called automatically when `main` returns.
+pub fn report() {
+    LowLevelDebug::<crate::TockSyscalls>::print_2(size() as u32, high_water_mark() as u32);
+}
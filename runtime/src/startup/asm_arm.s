@@ -11,6 +11,9 @@
  *     Start of .data in ram       |     20
  *     Size of .bss                |     24
  *     Start of .bss in ram        |     28
+ *
+ * Position-independent process binaries have three more fields (see
+ * asm_arm_pic.s).
  */
 
 /* start is the entry point -- the first code executed by the kernel. The kernel
@@ -23,12 +26,14 @@
  *     r2  Size of the process' allocated memory region (including grant region)
  *     r3  Process break provided by the kernel.
  *
- * We currently only use the value in r0. It is copied into r5 early on because
- * r0 is needed to invoke system calls.
+ * We use the value in r0, which is copied into r5 early on because r0 is needed
+ * to invoke system calls. r3 is only used if the process binary is not at the
+ * correct location.
  *
- * To be compatible with ARMv6 Thumb-1, we the cmp and beq instructions
- * instead of cbz in two places. This increases the code size with 4 bytes,
- * but allows us to use it on Cortex-M0+ processors.
+ * This code is shared by ARMv6-M (Cortex-M0/M0+) and ARMv7E-M process binaries,
+ * so it must only use the Thumb-1 instruction subset. For example, we use the
+ * cmp and beq instructions instead of cbz in two places. This increases the
+ * code size by 4 bytes, but allows us to use it on Cortex-M0+ processors.
  */
 .section .start, "ax"
 .global start
@@ -42,15 +47,18 @@ start:
 	ldr r0, [r5, #0]  /* r0 = rt_header.start */
 	adds r0, #3       /* r0 = rt_header.start + 4 - 1 (for Thumb bit) */
 	cmp r0, r4        /* Skip error handling if pc correct */
-	beq .Lset_brk     
+	beq .Lset_brk
 	/* If the beq on the previous line did not jump, then the binary is not at
-	 * the correct location. Report the error via LowLevelDebug then exit. */
-	movs r0, #8  /* LowLevelDebug driver number */
-	movs r1, #1  /* Command: print alert code */
-	movs r2, #2  /* Alert code 2 (incorrect location */
-	svc 2        /* Execute `command` */
-	movs r0, #0  /* Operation: exit-terminate */
-	svc 6        /* Execute `exit` */
+	 * the correct location. rust_wrong_location reports the error then exits.
+	 * It needs a stack, but the stack in rt_header is at the location the
+	 * process binary was linked for, which may not be accessible. Instead, the
+	 * stack starts at the process break provided by the kernel. */
+	subs r1, r0, #4   /* r1 = rt_header.start without the Thumb bit */
+	subs r0, r4, #4   /* r0 = actual address of start */
+	lsrs r3, r3, #3   /* Align the process break down to 8 bytes */
+	lsls r3, r3, #3
+	mov sp, r3
+	bl rust_wrong_location
 
 .Lset_brk:
 	/* memop(): set brk to rt_header's initial break value */
@@ -91,4 +99,5 @@ start:
 	bne .Lbss_loop_body        /* Iterate again if remaining != 0 */
 
 .Lcall_rust_start:
+	mov r0, r5                 /* Pass rt_header to rust_start */
 	bl rust_start
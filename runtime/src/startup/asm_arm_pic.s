@@ -0,0 +1,105 @@
+/* Startup code for position-independent process binaries, used in place of
+ * asm_arm.s when the `pic` feature is enabled. The process binary is built with
+ * LIBTOCK_PLATFORM=pic, which links FLASH at 0x80000000 and RAM at 0, so the
+ * RAM addresses in rt_header are offsets from the start of the process' RAM.
+ *
+ * rt_header is defined by the general linker script (libtock_layout.ld). It has
+ * the following layout:
+ *
+ *     Field                       | Offset
+ *     ------------------------------------
+ *     Address of the start symbol |      0
+ *     Initial process break       |      4
+ *     Top of the stack            |      8
+ *     Size of .data               |     12
+ *     Start of .data in flash     |     16
+ *     Start of .data in ram       |     20
+ *     Size of .bss                |     24
+ *     Start of .bss in ram        |     28
+ *     Address of rt_header        |     32
+ *     Start of .rel.dyn           |     36
+ *     Size of .rel.dyn            |     40
+ *
+ * All addresses are link-time addresses.
+ */
+
+/* start is the entry point -- the first code executed by the kernel. The kernel
+ * passes arguments through 4 registers:
+ *
+ *     r0  Pointer to beginning of the process binary's code. The linker script
+ *         locates rt_header at this address.
+ *
+ *     r1  Address of the beginning of the process's usable memory region.
+ *     r2  Size of the process' allocated memory region (including grant region)
+ *     r3  Process break provided by the kernel.
+ *
+ * We use the values in r0 and r1, which are copied into r5 and r6 because r0
+ * and r1 are needed to invoke system calls. Unlike the statically-linked
+ * startup code, this does not verify the process binary's location; instead,
+ * rust_start relocates the process binary to wherever it was loaded.
+ *
+ * To be compatible with ARMv6 Thumb-1, we use the cmp and beq instructions
+ * instead of cbz.
+ */
+.section .start, "ax"
+.global start
+.thumb_func
+start:
+	mov r5, r0        /* r5 = rt_header (runtime address) */
+	mov r6, r1        /* r6 = start of the process' RAM */
+
+	/* memop(): set brk to rt_header's initial break value */
+	movs r0, #0       /* operation: set break */
+	ldr r1, [r5, #4]  /* rt_header`s initial process break (RAM offset) */
+	adds r1, r6       /* Convert to a runtime address */
+	svc 5             /* call `memop` */
+
+	/* Set the stack pointer */
+	ldr r0, [r5, #8]  /* r0 = rt_header._stack_top (RAM offset) */
+	adds r0, r6       /* Convert to a runtime address */
+	mov sp, r0
+
+	/* Set the static base register. RWPI code addresses read-write data
+	 * relative to r9, and lld computes those offsets relative to the start of
+	 * the segment containing .data. */
+	ldr r0, [r5, #20]  /* r0 = rt_header.data_ram_start (RAM offset) */
+	adds r0, r6        /* Convert to a runtime address */
+	mov r9, r0
+
+	/* Copy .data into place */
+	ldr r0, [r5, #12]          /* remaining = rt_header.data_size */
+	cmp r0, #0                 /* Jump to zero_bss if remaining == 0 */
+	beq .Lzero_bss
+	ldr r1, [r5, #16]          /* r1 = rt_header.data_flash_start */
+	ldr r2, [r5, #32]          /* r2 = link address of rt_header */
+	subs r1, r2                /* r1 = offset of .data from rt_header */
+	adds r1, r5                /* src = runtime address of .data in flash */
+	ldr r2, [r5, #20]          /* r2 = rt_header.data_ram_start */
+	adds r2, r6                /* dest = runtime address of .data in RAM */
+.Ldata_loop_body:
+	ldr r3, [r1]               /* r3 = *src */
+	str r3, [r2]               /* *(dest) = r3 */
+	subs r0, #4                /* remaining -= 4 */
+	adds r1, #4                /* src += 4 */
+	adds r2, #4                /* dest += 4 */
+	cmp r0, #0
+	bne .Ldata_loop_body       /* Iterate again if remaining != 0 */
+
+.Lzero_bss:
+	ldr r0, [r5, #24]          /* remaining = rt_header.bss_size */
+	cmp r0, #0                 /* Jump to call_rust_start if remaining == 0 */
+	beq .Lcall_rust_start
+	ldr r1, [r5, #28]          /* r1 = rt_header.bss_start */
+	adds r1, r6                /* dest = runtime address of .bss */
+	movs r2, #0                /* r2 = 0 */
+.Lbss_loop_body:
+	strb r2, [r1]              /* *(dest) = r2 = 0 */
+	subs r0, #1                /* remaining -= 1 */
+	adds r1, #1                /* dest += 1 */
+	cmp r0, #0
+	bne .Lbss_loop_body        /* Iterate again if remaining != 0 */
+
+.Lcall_rust_start:
+	mov r0, r5                 /* rust_start's rt_header argument */
+	mov r1, r6                 /* rust_start's ram_start argument */
+	bl rust_start
@@ -11,6 +11,9 @@
  *     Start of .data in ram       |     20
  *     Size of .bss                |     24
  *     Start of .bss in ram        |     28
+ *
+ * Position-independent process binaries have three more fields (see
+ * asm_arm_pic.s).
  */
 
 /* start is the entry point -- the first code executed by the kernel. The kernel
@@ -23,8 +26,12 @@
  *     a2  Size of the process' allocated memory region (including grant region)
  *     a3  Process break provided by the kernel.
  *
- * We currently only use the value in a0. It is copied into a5 early on because
- * a0-a4 are needed to invoke system calls.
+ * We use the value in a0, which is copied into a5 early on because a0-a4 are
+ * needed to invoke system calls. a3 is only used if the process binary is not
+ * at the correct location.
+ *
+ * LLVM's assembler does not accept a comment on the same line as a mv
+ * instruction, so mv instructions are described by the preceding comment.
  */
 .section .start, "ax"
 .globl start
@@ -33,20 +40,20 @@ start:
 	 * check is performed by comparing the program counter at the start to the
 	 * address of `start`, which is stored in rt_header. */
 	auipc s0, 0            /* s0 = pc */
-	mv a5, a0;             /* Save rt_header so syscalls don't overwrite it */
+	/* Save rt_header so syscalls don't overwrite it */
+	mv a5, a0
 	lw s1, 0(a5)           /* s1 = rt_header.start */
 	beq s0, s1, .Lset_brk  /* Skip error handling code if pc is correct */
 	/* If the beq on the previous line did not jump, then the binary is not at
-	 * the correct location. Report the error via LowLevelDebug then exit. */
-	li a0, 8  /* LowLevelDebug driver number */
-	li a1, 1  /* Command: Print alert code */
-	li a2, 2  /* Alert code 2 (incorrect location) */
-	li a4, 2  /* `command` class */
-	ecall
-	li a0, 0  /* exit-terminate */
-	/* TODO: Set a completion code, once completion codes are decided */
-	li a4, 6  /* `exit` class */
-	ecall
+	 * the correct location. rust_wrong_location reports the error then exits.
+	 * It needs a stack, but the stack in rt_header is at the location the
+	 * process binary was linked for, which may not be accessible. Instead, the
+	 * stack starts at the process break provided by the kernel. */
+	/* a0 = actual address of start, a1 = rt_header.start */
+	mv a0, s0
+	mv a1, s1
+	andi sp, a3, -16           /* Align the process break down to 16 bytes */
+	jal rust_wrong_location
 
 .Lset_brk:
 	/* memop(): set brk to rt_header's initial break value */
@@ -82,5 +89,7 @@ start:
 	bnez a0, .Lbss_loop_body    /* Iterate again if remaining != 0 */
 
 .Lcall_rust_start:
-	/* Note: rust_start must be a diverging function (i.e. return `!`) */
+	/* Pass rt_header to rust_start. Note: rust_start must be a diverging
+	 * function (i.e. return `!`) */
+	mv a0, a5
 	jal rust_start
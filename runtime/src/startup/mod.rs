@@ -1,23 +1,63 @@
 //! Runtime components related to process startup.
 
+use crate::TockSyscalls;
+use libtock_platform::Syscalls;
+
 // Include the correct `start` symbol (the program entry point) for the
 // architecture.
-#[cfg(target_arch = "arm")]
+#[cfg(all(target_arch = "arm", not(feature = "pic")))]
 core::arch::global_asm!(include_str!("asm_arm.s"));
+#[cfg(all(target_arch = "arm", feature = "pic"))]
+core::arch::global_asm!(include_str!("asm_arm_pic.s"));
 #[cfg(target_arch = "riscv32")]
 core::arch::global_asm!(include_str!("asm_riscv32.s"));
 
+#[cfg(all(target_arch = "riscv32", feature = "pic"))]
+compile_error!("Position-independent process binaries are only supported on ARM");
+
+#[cfg(feature = "pic")]
+mod relocate;
+
+/// The completion code a process exits with if it was not loaded at the
+/// location it was linked for. It is distinct from the completion codes
+/// `Termination` uses for errors, which are `ErrorCode` values.
+pub const WRONG_LOCATION_EXIT_CODE: u32 = 0x402;
+
+/// The completion code a position-independent process binary exits with if it
+/// contains a relocation the runtime cannot apply (e.g. because it was not built
+/// with the ropi-rwpi relocation model).
+pub const RELOCATION_FAILED_EXIT_CODE: u32 = 0x403;
+
+// LowLevelDebug driver number, command numbers, and alert code used to report
+// startup errors. libtock_runtime does not depend on libtock_low_level_debug,
+// so these are defined here.
+const LOW_LEVEL_DEBUG: u32 = 8;
+const PRINT_ALERT_CODE: u32 = 1;
+#[cfg(not(feature = "pic"))]
+const PRINT_2: u32 = 3;
+const WRONG_LOCATION: u32 = 2;
+
 /// `set_main!` is used to tell `libtock_runtime` where the process binary's
 /// `main` function is. The process binary's `main` function must have the
 /// signature `FnOnce() -> T`, where T is some concrete type that implements
 /// `libtock_platform::Termination`.
 ///
+/// `main` may also be an `async fn`, in which case it must be prefixed with
+/// `async` in the `set_main!` invocation. The future it returns is run by
+/// `libtock_platform::future::block_on`.
+///
 /// # Example
 /// ```
 /// libtock_runtime::set_main!{main};
 ///
 /// fn main() -> () { /* Omitted */ }
 /// ```
+///
+/// ```
+/// libtock_runtime::set_main!{async main};
+///
+/// async fn main() -> () { /* Omitted */ }
+/// ```
 // set_main! generates a function called `libtock_unsafe_main`, which is called
 // by `rust_start`. The function has `unsafe` in its name because implementing
 // it is `unsafe` (it *must* have the signature `libtock_unsafe_main() -> !`),
@@ -26,13 +66,28 @@ core::arch::global_asm!(include_str!("asm_riscv32.s"));
 // signature.
 #[macro_export]
 macro_rules! set_main {
+    {async $name:ident} => {
+        #[no_mangle]
+        fn libtock_unsafe_main() -> ! {
+            use libtock_runtime::TockSyscalls;
+            let res = libtock_platform::future::block_on::<TockSyscalls, _>($name());
+            #[allow(unreachable_code)] // so that async fn main() -> ! does not produce a warning.
+            {
+                libtock_runtime::exit_hook();
+                libtock_platform::Termination::complete::<TockSyscalls>(res)
+            }
+        }
+    };
     {$name:ident} => {
         #[no_mangle]
         fn libtock_unsafe_main() -> ! {
             use libtock_runtime::TockSyscalls;
             let res = $name();
             #[allow(unreachable_code)] // so that fn main() -> ! does not produce a warning.
-            libtock_platform::Termination::complete::<TockSyscalls>(res)
+            {
+                libtock_runtime::exit_hook();
+                libtock_platform::Termination::complete::<TockSyscalls>(res)
+            }
         }
     }
 }
@@ -55,13 +110,81 @@ macro_rules! stack_size {
     }
 }
 
+/// Called by `libtock_unsafe_main` after `main` returns, before the process
+/// exits. Not called if the process exits from within `main` (e.g. by calling
+/// `exit_terminate`).
+#[doc(hidden)]
+pub fn exit_hook() {
+    #[cfg(feature = "stack_paint")]
+    crate::stack::report();
+}
+
+// Called by start if the process binary was not loaded at the location it was
+// linked for. This runs before .data and .bss are initialized, so it must not
+// access static variables. Reports the error via LowLevelDebug (if present),
+// printing the actual and expected addresses of start, then terminates the
+// process.
+#[cfg(not(feature = "pic"))]
+#[no_mangle]
+extern "C" fn rust_wrong_location(actual: usize, expected: usize) -> ! {
+    let _ = TockSyscalls::command(LOW_LEVEL_DEBUG, PRINT_ALERT_CODE, WRONG_LOCATION, 0);
+    let _ = TockSyscalls::command(LOW_LEVEL_DEBUG, PRINT_2, actual as u32, expected as u32);
+    TockSyscalls::exit_terminate(WRONG_LOCATION_EXIT_CODE)
+}
+
+// The runtime header, as defined by libtock_layout.ld. All addresses are
+// link-time addresses; in position-independent process binaries, RAM addresses
+// are offsets from the start of the process' RAM. Most fields are only used by
+// start.
+#[allow(dead_code)]
+#[repr(C)]
+struct RtHeader {
+    start: usize,
+    initial_break: usize,
+    stack_top: usize,
+    data_size: usize,
+    data_flash_start: usize,
+    data_ram_start: usize,
+    bss_size: usize,
+    bss_start: usize,
+    #[cfg(feature = "pic")]
+    rt_header: usize,
+    #[cfg(feature = "pic")]
+    rel_start: usize,
+    #[cfg(feature = "pic")]
+    rel_size: usize,
+}
+
 // rust_start is the first Rust code to execute in the process. It is called
-// from start, which is written directly in assembly.
+// from start, which is written directly in assembly, and which passes the
+// runtime address of rt_header. In position-independent process binaries, start
+// also passes the runtime address of the process' RAM, which rust_start uses to
+// relocate the process binary before any other Rust code runs.
 #[no_mangle]
-extern "C" fn rust_start() -> ! {
-    // TODO: Call memop() to inform the kernel of the stack and heap sizes +
-    // locations. Also, perhaps we should support calling a heap initialization
-    // function?
+extern "C" fn rust_start(
+    rt_header: *const RtHeader,
+    #[cfg(feature = "pic")] ram_start: usize,
+) -> ! {
+    // Safety: start passes the runtime addresses of rt_header and RAM, and has
+    // already copied .data into RAM.
+    #[cfg(feature = "pic")]
+    unsafe {
+        relocate::relocate(rt_header, ram_start);
+    }
+
+    #[cfg(feature = "stack_paint")]
+    crate::stack::paint();
+
+    // Tell the kernel where the stack and heap are.
+    // Safety: start passes the runtime address of rt_header, which is in flash
+    // and is never modified.
+    let header = unsafe { &*rt_header };
+    #[cfg(not(feature = "pic"))]
+    let ram_start = 0;
+    crate::memory_hints::report::<TockSyscalls>(header.stack_top, header.initial_break, ram_start);
+
+    // Run the functions registered with init_fn!.
+    crate::init::run();
 
     extern "Rust" {
         fn libtock_unsafe_main() -> !;
@@ -0,0 +1,46 @@
+//! Startup relocation for position-independent process binaries. The
+//! relocations are applied by `crate::relocation`.
+
+use super::RtHeader;
+use crate::relocation::{apply, Rel};
+use crate::TockSyscalls;
+use core::mem::size_of;
+use libtock_platform::Syscalls;
+
+/// Applies the relocations in `.rel.dyn` to `.data`.
+///
+/// # Safety
+/// `rt_header` must be the runtime address of `rt_header`, `ram_start` must be
+/// the runtime address of the start of the process' RAM, and `.data` must have
+/// been copied into RAM. Must be called before any code accesses `.data`.
+pub(super) unsafe fn relocate(rt_header: *const RtHeader, ram_start: usize) {
+    // Safety: The caller guarantees rt_header points to the runtime header.
+    let header = unsafe { &*rt_header };
+    // The difference between the runtime and link-time addresses of flash.
+    let flash_offset = (rt_header as usize).wrapping_sub(header.rt_header);
+    // Safety: .rel.dyn contains rel_size bytes of relocations, and is in flash,
+    // which is not modified.
+    let relocations = unsafe {
+        core::slice::from_raw_parts(
+            header.rel_start.wrapping_add(flash_offset) as *const Rel,
+            header.rel_size / size_of::<Rel>(),
+        )
+    };
+    // Safety: The linker only emits relocations for .data, which the caller
+    // guarantees has been copied into RAM.
+    if unsafe { apply(relocations, ram_start, flash_offset) }.is_err() {
+        unsupported_relocation();
+    }
+}
+
+// Reports an unsupported relocation, then terminates the process. Like the
+// statically-linked startup code's location check, this prints the wrong
+// location alert code via LowLevelDebug (if present), as the process binary
+// cannot run where it was loaded. It exits with its own completion code so
+// that the two errors can be told apart.
+#[cold]
+fn unsupported_relocation() -> ! {
+    use super::{LOW_LEVEL_DEBUG, PRINT_ALERT_CODE, RELOCATION_FAILED_EXIT_CODE, WRONG_LOCATION};
+    let _ = TockSyscalls::command(LOW_LEVEL_DEBUG, PRINT_ALERT_CODE, WRONG_LOCATION, 0);
+    TockSyscalls::exit_terminate(RELOCATION_FAILED_EXIT_CODE)
+}
@@ -94,10 +94,10 @@ extern "C" fn start_prototype(
     }
 
     extern "C" {
-        fn rust_start() -> !;
+        fn rust_start(rt_header: &RtHeader) -> !;
     }
 
     unsafe {
-        rust_start();
+        rust_start(rt_header);
     }
 }
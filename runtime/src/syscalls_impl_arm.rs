@@ -1,6 +1,11 @@
 use core::arch::asm;
 use libtock_platform::{syscall_class, RawSyscalls, Register};
 
+// These implementations are shared by ARMv6-M (Cortex-M0/M0+) and ARMv7E-M
+// process binaries, so they must only use the Thumb-1 instruction subset and
+// the low registers (r0-r7) as operands. svc is a 16-bit Thumb-1 instruction,
+// and all operands are in r0-r3.
+
 unsafe impl RawSyscalls for crate::TockSyscalls {
     unsafe fn yield1([Register(r0)]: [Register; 1]) {
         // Safety: This matches the invariants required by the documentation on
@@ -43,6 +48,23 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
         }
     }
 
+    unsafe fn yield3(
+        [Register(mut r0), Register(mut r1), Register(mut r2)]: [Register; 3],
+    ) -> [Register; 3] {
+        // Safety: This matches the invariants required by the documentation on
+        // RawSyscalls::yield3
+        unsafe {
+            asm!("svc 0",
+                 inlateout("r0") r0,
+                 inlateout("r1") r1,
+                 inlateout("r2") r2,
+                 lateout("r3") _,
+                 options(preserves_flags, nostack),
+            );
+        }
+        [Register(r0), Register(r1), Register(r2)]
+    }
+
     unsafe fn syscall1<const CLASS: usize>([Register(mut r0)]: [Register; 1]) -> [Register; 2] {
         let r1;
         // Safety: This matches the invariants required by the documentation on
@@ -127,6 +149,13 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
                      inlateout("r3") r3,
                      options(preserves_flags, nostack),
                 ),
+                syscall_class::ALLOW_UR => asm!("svc 7",
+                     inlateout("r0") r0,
+                     inlateout("r1") r1,
+                     inlateout("r2") r2,
+                     inlateout("r3") r3,
+                     options(preserves_flags, nostack),
+                ),
                 _ => unreachable!(),
             }
         }
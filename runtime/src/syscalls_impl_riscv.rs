@@ -68,6 +68,24 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
         }
     }
 
+    unsafe fn yield3(
+        [Register(mut r0), Register(mut r1), Register(mut r2)]: [Register; 3],
+    ) -> [Register; 3] {
+        // Safety: This matches the invariants required by the documentation on
+        // RawSyscalls::yield3
+        unsafe {
+            asm!("ecall",
+                 inlateout("a0") r0,
+                 inlateout("a1") r1,
+                 inlateout("a2") r2,
+                 lateout("a3") _,
+                 inlateout("a4") 0 => _,
+                 options(preserves_flags, nostack),
+            );
+        }
+        [Register(r0), Register(r1), Register(r2)]
+    }
+
     unsafe fn syscall1<const CLASS: usize>([Register(mut r0)]: [Register; 1]) -> [Register; 2] {
         let r1;
         // Safety: This matches the invariants required by the documentation on
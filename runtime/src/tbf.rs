@@ -0,0 +1,81 @@
+//! Macros that set options in the process binary's Tock Binary Format (TBF)
+//! headers. Each macro places its value in a dedicated ELF section, which the
+//! runner reads and passes to elf2tab. Process binaries that do not use these
+//! macros get the runner's defaults.
+
+/// Sets the size of the protected region at the start of the process binary,
+/// which contains the TBF headers. The layout file's `TBF_HEADER_SIZE` is used
+/// if this macro is not invoked. Example:
+/// ```
+/// tbf_header_size!{0x60}
+/// ```
+// tbf_header_size works like stack_size: it places a buffer of the given size
+// in the .tbf_header_buffer section, which the linker script uses to size the
+// .tbf_header section. The runner uses the size of the .tbf_header section as
+// elf2tab's --protected-region-size.
+#[macro_export]
+macro_rules! tbf_header_size {
+    {$size:expr} => {
+        #[no_mangle]
+        #[link_section = ".tbf_header_buffer"]
+        pub static mut TBF_HEADER_MEMORY: [u8; $size] = [0; $size];
+    }
+}
+
+/// Sets the process' name. If this macro is not invoked, the name of the
+/// process binary's ELF file is used. Example:
+/// ```
+/// app_name!{"blink"}
+/// ```
+#[macro_export]
+macro_rules! app_name {
+    {$name:expr} => {
+        #[no_mangle]
+        #[link_section = ".tbf_app_name"]
+        pub static TBF_APP_NAME: [u8; $name.len()] = $crate::__str_to_array($name);
+    }
+}
+
+/// Sets the minimum amount of RAM, in bytes, the kernel must allocate to the
+/// process. This is useful for processes that use a heap. If this macro is not
+/// invoked, elf2tab computes the minimum from the size of the process binary's
+/// RAM sections. Example:
+/// ```
+/// minimum_ram_size!{0x2000}
+/// ```
+#[macro_export]
+macro_rules! minimum_ram_size {
+    {$size:expr} => {
+        #[no_mangle]
+        #[link_section = ".tbf_minimum_ram_size"]
+        pub static TBF_MINIMUM_RAM_SIZE: u32 = $size;
+    }
+}
+
+/// Sets the kernel version the process requires, as a (major, minor) pair. If
+/// this macro is not invoked, the runner requires Tock 2.1, which the
+/// libtock-rs crates are designed for. Example:
+/// ```
+/// kernel_version!{2, 1}
+/// ```
+#[macro_export]
+macro_rules! kernel_version {
+    {$major:expr, $minor:expr} => {
+        #[no_mangle]
+        #[link_section = ".tbf_kernel_version"]
+        pub static TBF_KERNEL_VERSION: [u32; 2] = [$major, $minor];
+    }
+}
+
+// Converts a string into a byte array, for use in app_name!.
+#[doc(hidden)]
+pub const fn __str_to_array<const N: usize>(string: &str) -> [u8; N] {
+    let bytes = string.as_bytes();
+    let mut array = [0; N];
+    let mut i = 0;
+    while i < N {
+        array[i] = bytes[i];
+        i += 1;
+    }
+    array
+}
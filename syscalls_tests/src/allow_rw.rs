@@ -1,4 +1,4 @@
-use libtock_platform::{allow_rw, share, CommandReturn, ErrorCode, Syscalls};
+use libtock_platform::{allow_rw, share, CommandReturn, DefaultConfig, ErrorCode, Syscalls};
 use libtock_unittest::{command_return, fake, RwAllowBuffer, SyscallLogEntry};
 use std::cell::Cell;
 use std::rc::Rc;
@@ -127,3 +127,56 @@ fn allow_rw() {
     // Verify the buffer write occurred.
     assert_eq!(buffer2, [5, 31]);
 }
+
+#[test]
+fn static_allow_rw() {
+    use allow_rw::StaticAllowRw;
+    let kernel = fake::Kernel::new();
+    let driver = Rc::new(TestDriver::default());
+    kernel.add_driver(&driver);
+
+    // Tests a call that should fail because it has an incorrect buffer number.
+    // The buffer should be returned.
+    let buffer = Box::leak(Box::new([1, 2, 3, 4]));
+    let (buffer, error) = match StaticAllowRw::<fake::Syscalls, 42, 1>::new::<DefaultConfig>(buffer)
+    {
+        Ok(_) => panic!("allow with incorrect buffer number succeeded"),
+        Err(result) => result,
+    };
+    assert_eq!(error, ErrorCode::NoSupport);
+    assert_eq!(buffer, [1, 2, 3, 4]);
+    kernel.take_syscall_log();
+
+    // Share the buffer, then let the driver write to it.
+    let allow = match StaticAllowRw::<fake::Syscalls, 42, 0>::new::<DefaultConfig>(buffer) {
+        Ok(allow) => allow,
+        Err((_, error)) => panic!("allow failed: {:?}", error),
+    };
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowRw {
+            driver_num: 42,
+            buffer_num: 0,
+            len: 4,
+        }]
+    );
+    let mut driver_buffer = driver.buffer_0.take();
+    driver_buffer[2] = 31;
+    driver.buffer_0.set(driver_buffer);
+
+    // Revoke the buffer, and verify the driver's write is visible.
+    let buffer = allow.revoke();
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowRw {
+            driver_num: 42,
+            buffer_num: 0,
+            len: 0,
+        }]
+    );
+    assert_eq!(driver.buffer_0.take().len(), 0);
+    assert_eq!(buffer, [1, 2, 31, 4]);
+
+    // Safety: buffer came from Box::leak, and has been revoked.
+    drop(unsafe { Box::from_raw(buffer) });
+}
@@ -0,0 +1,179 @@
+use libtock_platform::{allow_ur, share, CommandReturn, ErrorCode, Syscalls};
+use libtock_unittest::{command_return, fake, SyscallLogEntry, UrAllowBuffer};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::thread_local;
+
+#[derive(Default)]
+struct TestDriver {
+    buffer_0: Cell<UrAllowBuffer>,
+}
+
+impl fake::SyscallDriver for TestDriver {
+    fn id(&self) -> u32 {
+        42
+    }
+
+    fn num_upcalls(&self) -> u32 {
+        0
+    }
+
+    fn command(&self, _command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        command_return::failure(ErrorCode::NoSupport)
+    }
+
+    fn allow_userspace_readable(
+        &self,
+        buffer_num: u32,
+        buffer: UrAllowBuffer,
+    ) -> Result<UrAllowBuffer, (UrAllowBuffer, ErrorCode)> {
+        if buffer_num != 0 {
+            return Err((buffer, ErrorCode::NoSupport));
+        }
+        Ok(self.buffer_0.replace(buffer))
+    }
+}
+
+struct TestConfig;
+
+// CALLED is set to true when returned_nonzero_buffer is called.
+thread_local! {static CALLED: Cell<bool> = Cell::new(false); }
+
+impl allow_ur::Config for TestConfig {
+    fn returned_nonzero_buffer(driver_num: u32, buffer_num: u32) {
+        assert_eq!(driver_num, 42);
+        assert_eq!(buffer_num, 0);
+        CALLED.with(|cell| cell.set(true));
+    }
+}
+
+#[test]
+fn allow_ur() {
+    let kernel = fake::Kernel::new();
+    let driver = Rc::new(TestDriver::default());
+    kernel.add_driver(&driver);
+    let buffer1 = [1, 2, 3, 4].map(Cell::new);
+    let buffer2 = [5, 6].map(Cell::new);
+    share::scope(|allow_ur| {
+        // Tests a call that should fail because it has an incorrect buffer
+        // number.
+        let result = fake::Syscalls::allow_ur::<TestConfig, 42, 1>(allow_ur, &buffer1);
+        assert!(!CALLED.with(|c| c.get()));
+        assert_eq!(result, Err(ErrorCode::NoSupport));
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowUr {
+                driver_num: 42,
+                buffer_num: 1,
+                len: 4,
+            }]
+        );
+    });
+
+    // Verify that share::scope unallowed the buffer.
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowUr {
+            driver_num: 42,
+            buffer_num: 1,
+            len: 0,
+        }]
+    );
+
+    share::scope(|allow_ur| {
+        // Tests a call that should succeed and return a zero buffer.
+        let result = fake::Syscalls::allow_ur::<TestConfig, 42, 0>(allow_ur, &buffer1);
+        assert!(!CALLED.with(|c| c.get()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowUr {
+                driver_num: 42,
+                buffer_num: 0,
+                len: 4,
+            }]
+        );
+
+        // Tests a call that should succeed and return a nonzero buffer.
+        let result = fake::Syscalls::allow_ur::<TestConfig, 42, 0>(allow_ur, &buffer2);
+        assert!(CALLED.with(|c| c.get()));
+        assert_eq!(result, Ok(()));
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowUr {
+                driver_num: 42,
+                buffer_num: 0,
+                len: 2,
+            }]
+        );
+
+        // Mutate the buffer through the driver, and verify the process can
+        // observe the write while the buffer is still shared. Under Miri, this
+        // verifies the buffer has been shared with the kernel properly.
+        let buffer = driver.buffer_0.take();
+        buffer[1].set(31);
+        driver.buffer_0.set(buffer);
+        assert_eq!(buffer2[1].get(), 31);
+    });
+
+    // Verify that share::scope unallowed the buffer, but only once.
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowUr {
+            driver_num: 42,
+            buffer_num: 0,
+            len: 0,
+        }]
+    );
+
+    // Verify the buffer write occurred.
+    assert_eq!(buffer2.map(|cell| cell.get()), [5, 31]);
+}
+
+#[test]
+fn static_allow_ur() {
+    use allow_ur::StaticAllowUr;
+    let kernel = fake::Kernel::new();
+    let driver = Rc::new(TestDriver::default());
+    kernel.add_driver(&driver);
+
+    // Tests a call that should fail because it has an incorrect buffer number.
+    let buffer: &'static [Cell<u8>] = Box::leak(Box::new([1, 2, 3, 4].map(Cell::new)));
+    assert!(matches!(
+        StaticAllowUr::<fake::Syscalls, 42, 1>::new::<TestConfig>(buffer),
+        Err(ErrorCode::NoSupport)
+    ));
+    kernel.take_syscall_log();
+
+    // Share the buffer, and verify the process can observe the driver's writes
+    // while it is shared.
+    let allow = match StaticAllowUr::<fake::Syscalls, 42, 0>::new::<TestConfig>(buffer) {
+        Ok(allow) => allow,
+        Err(error) => panic!("allow failed: {:?}", error),
+    };
+    let driver_buffer = driver.buffer_0.take();
+    driver_buffer[2].set(31);
+    driver.buffer_0.set(driver_buffer);
+    assert_eq!(allow.buffer()[2].get(), 31);
+
+    // A scoped share with the same numbers replaces the static buffer, and
+    // leaves nothing shared when its scope ends.
+    let scoped_buffer = [5, 6].map(Cell::new);
+    share::scope(|allow_ur| {
+        fake::Syscalls::allow_ur::<TestConfig, 42, 0>(allow_ur, &scoped_buffer).unwrap();
+    });
+    assert_eq!(driver.buffer_0.take().len(), 0);
+    kernel.take_syscall_log();
+
+    // Revoke the buffer.
+    let buffer = allow.revoke();
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowUr {
+            driver_num: 42,
+            buffer_num: 0,
+            len: 0,
+        }]
+    );
+    assert_eq!(buffer[2].get(), 31);
+}
@@ -0,0 +1,108 @@
+//! Tests for the `driver!` macro, using the skeleton fake driver it generates.
+
+use libtock_platform::{share, subscribe::FnUpcall, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::{command_return, fake, upcall, SyscallLogEntry};
+use std::cell::Cell;
+
+libtock_platform::driver! {
+    /// A driver that exercises every section of `driver!`.
+    pub struct TestDriver;
+
+    const DRIVER_NUM: u32 = 0x1234;
+
+    commands {
+        /// Returns whether the driver is present.
+        EXISTS = 0 => pub fn exists();
+        GET = 1 => pub fn get(index: u32) -> u32;
+        SET = 2 => pub fn set(index: u32, value: bool) -> (u32, u32);
+    }
+
+    subscribes {
+        DONE = 0 => pub fn register_done;
+        ERROR = 2 => pub fn register_error;
+    }
+
+    allow_ro {
+        INPUT = 1 => pub fn allow_input;
+    }
+
+    allow_rw {
+        OUTPUT = 3 => pub fn allow_output;
+    }
+
+    fake FakeTestDriver;
+}
+
+type Driver = TestDriver<fake::Syscalls>;
+
+#[test]
+fn commands() {
+    let kernel = fake::Kernel::new();
+    assert_eq!(Driver::exists(), Err(ErrorCode::NoDevice));
+
+    let driver = FakeTestDriver::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Driver::exists(), Ok(()));
+    assert_eq!(Driver::get(7), Err(ErrorCode::NoSupport));
+    driver.set_command_return(GET, command_return::success_u32(5));
+    assert_eq!(Driver::get(7), Ok(5));
+    driver.set_command_return(SET, command_return::success_2_u32(1, 2));
+    assert_eq!(Driver::set(3, true), Ok((1, 2)));
+    assert_eq!(
+        driver.take_command_log(),
+        [(0, 0, 0), (1, 7, 0), (1, 7, 0), (2, 3, 1)]
+    );
+    assert_eq!(
+        kernel.take_syscall_log().last(),
+        Some(&SyscallLogEntry::Command {
+            driver_id: 0x1234,
+            command_id: 2,
+            argument0: 3,
+            argument1: 1,
+        })
+    );
+}
+
+#[test]
+fn subscribes() {
+    let kernel = fake::Kernel::new();
+    let driver = FakeTestDriver::new();
+    kernel.add_driver(&driver);
+
+    let error = Cell::new(None);
+    let upcall = FnUpcall(|arg0, _, _| error.set(Some(arg0)));
+    share::scope(|subscribe| {
+        assert_eq!(Driver::register_error(&upcall, subscribe), Ok(()));
+        upcall::schedule(DRIVER_NUM, ERROR, (4, 0, 0)).unwrap();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    assert_eq!(error.get(), Some(4));
+
+    // Subscribe numbers beyond the largest declared subscribe number are
+    // rejected.
+    assert!(upcall::schedule(DRIVER_NUM, 3, (0, 0, 0)).is_err());
+}
+
+#[test]
+fn allows() {
+    let kernel = fake::Kernel::new();
+    let driver = FakeTestDriver::new();
+    kernel.add_driver(&driver);
+
+    let input = [1, 2, 3];
+    let mut output = [0; 2];
+    share::scope(|allow_ro| {
+        share::scope(|allow_rw| {
+            assert_eq!(Driver::allow_input(allow_ro, &input), Ok(()));
+            assert_eq!(Driver::allow_output(allow_rw, &mut output), Ok(()));
+            assert_eq!(driver.ro_buffer(INPUT), Some(vec![1, 2, 3]));
+            assert_eq!(
+                driver.with_rw_buffer(OUTPUT, |buffer| buffer[1] = 9),
+                Some(())
+            );
+            assert_eq!(driver.with_rw_buffer(0, |_| ()), None);
+        });
+    });
+    assert_eq!(output, [0, 9]);
+    assert_eq!(driver.ro_buffer(INPUT), Some(vec![]));
+}
@@ -0,0 +1,171 @@
+//! Tests for `libtock_platform::future`.
+
+use libtock_platform::future::{block_on, SubscribeFuture};
+use libtock_platform::{CommandReturn, DefaultConfig, ErrorCode, Syscalls};
+use libtock_unittest::{command_return, fake, upcall, SyscallLogEntry};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+// Fake driver that accepts an upcall. The test cases invoke the upcall
+// themselves.
+struct MockDriver;
+
+impl fake::SyscallDriver for MockDriver {
+    fn id(&self) -> u32 {
+        1
+    }
+
+    fn num_upcalls(&self) -> u32 {
+        1
+    }
+
+    fn command(&self, _: u32, _: u32, _: u32) -> CommandReturn {
+        command_return::failure(ErrorCode::NoSupport)
+    }
+}
+
+// Polls `future` once, using a waker that records whether it was woken.
+fn poll_once<F: Future>(future: Pin<&mut F>, waker: &Arc<TestWaker>) -> Poll<F::Output> {
+    future.poll(&mut Context::from_waker(&Waker::from(waker.clone())))
+}
+
+#[derive(Default)]
+struct TestWaker {
+    woken: AtomicBool,
+}
+
+impl Wake for TestWaker {
+    fn wake(self: Arc<Self>) {
+        self.woken.store(true, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn block_on_upcall() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let future = SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| {
+        upcall::schedule(1, 0, (2, 3, 4)).unwrap();
+        Ok(())
+    });
+    assert_eq!(block_on::<fake::Syscalls, _>(future), Ok((2, 3, 4)));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::YieldWait,
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn async_block() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let output = block_on::<fake::Syscalls, _>(async {
+        let mut sum = 0;
+        for i in 0..3 {
+            let (arg0, _, _) =
+                SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| {
+                    upcall::schedule(1, 0, (i, 0, 0)).unwrap();
+                    Ok(())
+                })
+                .await?;
+            sum += arg0;
+        }
+        Ok::<u32, ErrorCode>(sum)
+    });
+    assert_eq!(output, Ok(3));
+}
+
+#[test]
+fn drop_unsubscribes() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let waker = Arc::new(TestWaker::default());
+    let mut future =
+        Box::pin(SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| Ok(())));
+    assert_eq!(poll_once(future.as_mut(), &waker), Poll::Pending);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::Subscribe {
+            driver_num: 1,
+            subscribe_num: 0,
+        }]
+    );
+    drop(future);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::Subscribe {
+            driver_num: 1,
+            subscribe_num: 0,
+        }]
+    );
+    // The upcall was unsubscribed, so scheduling it does nothing.
+    upcall::schedule(1, 0, (2, 3, 4)).unwrap();
+    assert!(!waker.woken.load(Ordering::Relaxed));
+
+    // A future that is never polled never subscribes.
+    drop(SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| Ok(())));
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn start_failed() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let future =
+        SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| Err(ErrorCode::Busy));
+    assert_eq!(block_on::<fake::Syscalls, _>(future), Err(ErrorCode::Busy));
+    // The upcall should be subscribed then unsubscribed, with no yield.
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn subscribe_failed() {
+    let _kernel = fake::Kernel::new();
+    let future = SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| {
+        panic!("start called after a failed subscribe")
+    });
+    assert_eq!(block_on::<fake::Syscalls, _>(future), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn wakes_waker() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let waker = Arc::new(TestWaker::default());
+    let mut future =
+        Box::pin(SubscribeFuture::<fake::Syscalls, DefaultConfig, 1, 0, _>::new(|| Ok(())));
+    assert_eq!(poll_once(future.as_mut(), &waker), Poll::Pending);
+    upcall::schedule(1, 0, (5, 6, 7)).unwrap();
+    assert!(!waker.woken.load(Ordering::Relaxed));
+    fake::Syscalls::yield_wait();
+    assert!(waker.woken.load(Ordering::Relaxed));
+    assert_eq!(
+        poll_once(future.as_mut(), &waker),
+        Poll::Ready(Ok((5, 6, 7)))
+    );
+}
@@ -16,18 +16,31 @@ mod allow_ro;
 #[cfg(test)]
 mod allow_rw;
 
+#[cfg(test)]
+mod allow_ur;
+
 #[cfg(test)]
 mod command_tests;
 
+#[cfg(test)]
+mod driver_tests;
+
 #[cfg(test)]
 mod exit_on_drop;
 
 // TODO: Add Exit.
 
-// TODO: Add Memop.
+#[cfg(test)]
+mod future_tests;
+
+#[cfg(test)]
+mod memop_tests;
 
 #[cfg(test)]
 mod subscribe_tests;
 
+#[cfg(test)]
+mod termination;
+
 #[cfg(test)]
 mod yield_tests;
@@ -0,0 +1,112 @@
+//! Tests for the Memop system call implementation in
+//! `libtock_platform::Syscalls`.
+
+use libtock_platform::{memop_id, ErrorCode, Syscalls};
+use libtock_unittest::{fake, ExpectedSyscall, SyscallLogEntry};
+
+#[test]
+fn break_operations() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u8; 32];
+    let ram_start = ram.as_mut_ptr();
+    kernel.set_memory_layout(fake::MemoryLayout {
+        ram_start,
+        ram_end: ram_start.wrapping_add(32),
+        grant_region_start: ram_start.wrapping_add(16),
+        ..Default::default()
+    });
+
+    assert_eq!(unsafe { fake::Syscalls::memop_sbrk(8) }, Ok(ram_start));
+    assert_eq!(
+        unsafe { fake::Syscalls::memop_sbrk(-4) },
+        Ok(ram_start.wrapping_add(8))
+    );
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(4));
+    assert_eq!(
+        unsafe { fake::Syscalls::memop_sbrk(13) },
+        Err(ErrorCode::NoMem)
+    );
+    assert_eq!(
+        unsafe { fake::Syscalls::memop_brk(ram_start.wrapping_add(16)) },
+        Ok(())
+    );
+    assert_eq!(
+        unsafe { fake::Syscalls::memop_brk(ram_start.wrapping_add(17)) },
+        Err(ErrorCode::NoMem)
+    );
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(16));
+
+    // The break pointer returned by sbrk must be usable to access the memory
+    // between ram_start and the break.
+    let old_break = unsafe { fake::Syscalls::memop_sbrk(0) }.unwrap();
+    unsafe { old_break.sub(1).write(7) };
+    assert_eq!(ram[15], 7);
+}
+
+#[test]
+fn debug_hints() {
+    let kernel = fake::Kernel::new();
+    let stack_top = 0x2000_0400usize as *const u8;
+    let heap_start = 0x2000_0800usize as *const u8;
+    assert_eq!(fake::Syscalls::memop_debug_stack_start(stack_top), Ok(()));
+    assert_eq!(fake::Syscalls::memop_debug_heap_start(heap_start), Ok(()));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_STACK_START,
+                argument0: 0x2000_0400,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::DEBUG_HEAP_START,
+                argument0: 0x2000_0800,
+            },
+        ]
+    );
+}
+
+#[test]
+fn layout_queries() {
+    let kernel = fake::Kernel::new();
+    kernel.set_memory_layout(fake::MemoryLayout {
+        ram_start: 0x2000_0000usize as *mut u8,
+        ram_end: 0x2000_4000usize as *const u8,
+        grant_region_start: 0x2000_3000usize as *const u8,
+        flash_start: 0x3_0000usize as *const u8,
+        flash_end: 0x4_0000usize as *const u8,
+    });
+    assert_eq!(
+        fake::Syscalls::memop_process_ram_start(),
+        Ok(0x2000_0000usize as *const u8)
+    );
+    assert_eq!(
+        fake::Syscalls::memop_process_ram_end(),
+        Ok(0x2000_4000usize as *const u8)
+    );
+    assert_eq!(
+        fake::Syscalls::memop_flash_start(),
+        Ok(0x3_0000usize as *const u8)
+    );
+    assert_eq!(
+        fake::Syscalls::memop_flash_end(),
+        Ok(0x4_0000usize as *const u8)
+    );
+    assert_eq!(
+        fake::Syscalls::memop_grant_region_start(),
+        Ok(0x2000_3000usize as *const u8)
+    );
+}
+
+#[test]
+fn return_error() {
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::PROCESS_RAM_START,
+        argument0: 0,
+        return_error: Some(ErrorCode::NoSupport),
+    });
+    assert_eq!(
+        fake::Syscalls::memop_process_ram_start(),
+        Err(ErrorCode::NoSupport)
+    );
+}
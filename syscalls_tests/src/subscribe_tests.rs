@@ -103,6 +103,46 @@ fn success() {
     assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
 }
 
+#[test]
+fn static_subscribe() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let called_ptr: *mut core::cell::Cell<Option<(u32,)>> =
+        Box::into_raw(Box::new(core::cell::Cell::new(None)));
+    // Safety: called_ptr came from Box::into_raw, and is not deallocated until
+    // the end of the test.
+    let called = unsafe { &*called_ptr };
+    assert_eq!(
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 1, 0>(share::Handle::new_static(), called),
+        Ok(())
+    );
+    upcall::schedule(1, 0, (2, 3, 4)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    assert_eq!(called.get(), Some((2,)));
+
+    fake::Syscalls::unsubscribe(1, 0);
+    upcall::schedule(1, 0, (2, 3, 4)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0
+            },
+            SyscallLogEntry::YieldNoWait,
+            SyscallLogEntry::Subscribe {
+                driver_num: 1,
+                subscribe_num: 0
+            },
+            SyscallLogEntry::YieldNoWait,
+        ]
+    );
+
+    // Safety: called_ptr came from Box::into_raw, and has been unsubscribed.
+    drop(unsafe { Box::from_raw(called_ptr) });
+}
+
 #[cfg(not(miri))]
 #[test]
 fn unwinding_upcall() {
@@ -129,3 +169,31 @@ fn unwinding_upcall() {
     });
     assert_eq!(exit, libtock_unittest::ExitCall::Terminate(0));
 }
+
+#[test]
+fn static_subscribe_wrapper() {
+    use subscribe::StaticSubscribe;
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    let called: &'static core::cell::Cell<Option<(u32,)>> =
+        Box::leak(Box::new(core::cell::Cell::new(None)));
+
+    // Tests a call that should fail because it has an incorrect subscribe
+    // number.
+    assert!(matches!(
+        StaticSubscribe::<fake::Syscalls, _, 1, 1>::new::<_, DefaultConfig>(called),
+        Err(ErrorCode::Invalid)
+    ));
+
+    let subscribe = StaticSubscribe::<fake::Syscalls, _, 1, 0>::new::<_, DefaultConfig>(called)
+        .expect("subscribe failed");
+    upcall::schedule(1, 0, (2, 3, 4)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    assert_eq!(called.get(), Some((2,)));
+
+    // After unsubscribe, the upcall is no longer invoked.
+    let upcall = subscribe.unsubscribe();
+    upcall::schedule(1, 0, (5, 6, 7)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    assert_eq!(upcall.get(), Some((2,)));
+}
@@ -0,0 +1,72 @@
+use libtock_platform::{ErrorCode, Restart, Termination};
+use libtock_unittest::{exit_test, fake, ExitCall};
+
+// An application-defined error type, which is reported using a custom exit
+// code.
+struct AppError;
+
+impl From<AppError> for u32 {
+    fn from(_: AppError) -> u32 {
+        0x1234
+    }
+}
+
+#[cfg(not(miri))]
+#[test]
+fn unit() {
+    let exit = exit_test("termination::unit", || {
+        let _kernel = fake::Kernel::new();
+        ().complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Terminate(0));
+}
+
+#[cfg(not(miri))]
+#[test]
+fn result_ok() {
+    let exit = exit_test("termination::result_ok", || {
+        let _kernel = fake::Kernel::new();
+        Ok::<(), ErrorCode>(()).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Terminate(0));
+}
+
+#[cfg(not(miri))]
+#[test]
+fn result_error_code() {
+    let exit = exit_test("termination::result_error_code", || {
+        let _kernel = fake::Kernel::new();
+        Err::<(), _>(ErrorCode::NoMem).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Terminate(ErrorCode::NoMem as u32));
+}
+
+#[cfg(not(miri))]
+#[test]
+fn result_app_error() {
+    let exit = exit_test("termination::result_app_error", || {
+        let _kernel = fake::Kernel::new();
+        Err::<(), _>(AppError).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Terminate(0x1234));
+}
+
+#[cfg(not(miri))]
+#[test]
+fn restart_unit() {
+    let exit = exit_test("termination::restart_unit", || {
+        let _kernel = fake::Kernel::new();
+        Restart(()).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Restart(0));
+}
+
+#[cfg(not(miri))]
+#[test]
+fn restart_result() {
+    let exit = exit_test("termination::restart_result", || {
+        let _kernel = fake::Kernel::new();
+        Restart(Err::<(), _>(AppError)).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit, ExitCall::Restart(0x1234));
+}
@@ -36,3 +36,22 @@ fn wait() {
     fake::Syscalls::yield_wait();
     assert_eq!(kernel.take_syscall_log(), [SyscallLogEntry::YieldWait]);
 }
+
+// Tests yield_wait_for.
+#[test]
+fn wait_for() {
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWaitFor {
+        driver_num: 1,
+        subscribe_num: 2,
+        override_return: Some((3, 4, 5)),
+    });
+    assert_eq!(fake::Syscalls::yield_wait_for(1, 2), (3, 4, 5));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::YieldWaitFor {
+            driver_num: 1,
+            subscribe_num: 2
+        }]
+    );
+}
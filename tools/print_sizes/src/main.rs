@@ -1,5 +1,9 @@
 // Architectures that we expect the examples to be built for.
-const ARCHITECTURES: [&str; 2] = ["riscv32imc-unknown-none-elf", "thumbv7em-none-eabi"];
+const ARCHITECTURES: [&str; 3] = [
+    "riscv32imc-unknown-none-elf",
+    "thumbv6m-none-eabi",
+    "thumbv7em-none-eabi",
+];
 
 // The order of these fields actually matters, because it affects the derived
 // Ord impl. I have a suspicion that when I introduce size diffs into the CI,
@@ -12,18 +12,19 @@ use libtock_platform::Register;
 // TRD 104 (Tock's system call ABI) says that allow buffers only overlap if they
 // have a memory address in common, so zero-sized buffers cannot overlap.
 //
-// Read-Write Allow and Read-Only Allow are invoked through
-// RawSyscalls::syscall4, which is unsafe, and requires its caller to pass
-// arguments that are valid for the system call. Those requirements require that
-// either the length field is zero, or the address and length field represent a
-// valid slice. Several of the steps in this file require that property.
+// Read-Write Allow, Read-Only Allow, and Userspace-Readable Allow are invoked
+// through RawSyscalls::syscall4, which is unsafe, and requires its caller to
+// pass arguments that are valid for the system call. Those requirements require
+// that either the length field is zero, or the address and length field
+// represent a valid slice. Several of the steps in this file require that
+// property.
 //
 // Therefore AllowDb does not need to check for overlaps with zero-sized
 // buffers.
 #[derive(Default)]
 pub struct AllowDb {
-    // List of all active buffers, excluding zero-sized buffers. Contains both
-    // read-only buffers and read-write buffers.
+    // List of all active buffers, excluding zero-sized buffers. Contains
+    // read-only, read-write, and userspace-readable buffers.
     // Key: address of the buffer.
     // Value: length of the buffer.
     // Invariant: These buffers never overlap, and represent valid slices
@@ -124,6 +125,29 @@ impl AllowDb {
         Ok(RwAllowBuffer { address, len })
     }
 
+    /// Adds a userspace-readable buffer to the database, and returns it as a
+    /// `UrAllowBuffer`.
+    ///
+    /// # Safety
+    /// `address` and `len` must be valid as specified in TRD 104: either `len`
+    /// is 0 or `address` and `len` represent a valid slice.
+    pub unsafe fn insert_ur_buffer(
+        &mut self,
+        address: Register,
+        len: Register,
+    ) -> Result<UrAllowBuffer, OverlapError> {
+        let address: *mut u8 = address.into();
+        let len: usize = len.into();
+        if let Some(nonzero_len) = NonZeroUsize::new(len) {
+            // The buffer is not zero-sized. Add it to the database (checking it
+            // does not overlap an existing buffer).
+            // Safety: `len` is nonzero, so by this function's precondition
+            // `address` and `len` represent a valid slice.
+            unsafe { self.insert_raw(address, nonzero_len) }?;
+        }
+        Ok(UrAllowBuffer { address, len })
+    }
+
     /// Removes a read-only buffer from the database and returns its raw
     /// register values.
     ///
@@ -143,6 +167,16 @@ impl AllowDb {
         self.buffers.remove(&buffer.address);
         (buffer.address.into(), buffer.len.into())
     }
+
+    /// Removes a userspace-readable buffer from the database and returns its
+    /// raw register values.
+    ///
+    /// The returned value is the tuple (address, len) passed into the
+    /// insert_ur_buffer call that created the UrAllowBuffer.
+    pub fn remove_ur_buffer(&mut self, buffer: UrAllowBuffer) -> (Register, Register) {
+        self.buffers.remove(&buffer.address);
+        (buffer.address.into(), buffer.len.into())
+    }
 }
 
 #[derive(Debug, PartialEq, thiserror::Error)]
@@ -232,3 +266,42 @@ impl std::ops::DerefMut for RwAllowBuffer {
         }
     }
 }
+
+/// A reference to a buffer that has been shared via the Userspace-Readable
+/// Allow system call. Because the process may continue to read the buffer while
+/// it is shared, the buffer is accessed as a slice of `Cell`s rather than a
+/// `&mut [u8]`. This reference is non-Copy, so `AllowDb` can determine when all
+/// references to the buffer have been destroyed.
+#[derive(Debug)]
+pub struct UrAllowBuffer {
+    // Safety invariant: Either length is 0, or address and length can be
+    // soundly converted to a &'static [Cell<u8>]. Note: that means that no
+    // references other than &[Cell<u8>] references may overlap the slice
+    // described by address and len.
+    address: *mut u8,
+    len: usize,
+}
+
+impl Default for UrAllowBuffer {
+    fn default() -> UrAllowBuffer {
+        UrAllowBuffer {
+            address: core::ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+// Allows access to the pointed-to-buffer. The returned reference has the same
+// lifetime as the &self reference, so the caller can't keep the reference for
+// longer than it has access to the UrAllowBuffer.
+impl std::ops::Deref for UrAllowBuffer {
+    type Target = [core::cell::Cell<u8>];
+    fn deref(&self) -> &[core::cell::Cell<u8>] {
+        match self.len {
+            0 => &[],
+            // Safety: Because length is nonzero, the safety invariant on
+            // address and len says this conversion is sound.
+            _ => unsafe { core::slice::from_raw_parts(self.address as *const _, self.len) },
+        }
+    }
+}
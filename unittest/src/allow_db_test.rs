@@ -29,6 +29,19 @@ unsafe fn insert_rw_slice(
     unsafe { db.insert_rw_buffer(slice.as_ptr().into(), slice.len().into()) }
 }
 
+// Utility to call insert_ur_buffer with a slice.
+// Safety: insert_ur_slice does not prevent UrAllowBuffer from outliving slice.
+// Instead, the caller must make sure its use patterns don't cause invalid
+// accesses.
+unsafe fn insert_ur_slice(
+    db: &mut AllowDb,
+    slice: &[Cell<u8>],
+) -> Result<UrAllowBuffer, OverlapError> {
+    // Safety: The address and len arguments are derived directly from a slice,
+    // and therefore satisfy insert_ur_buffer's precondition.
+    unsafe { db.insert_ur_buffer(slice.as_ptr().into(), slice.len().into()) }
+}
+
 // Utility to return a RoAllowBuffer and verify the returned register values
 // match the provided slice.
 fn remove_ro_check(db: &mut AllowDb, buffer: RoAllowBuffer, slice: &[Cell<u8>]) {
@@ -49,6 +62,16 @@ fn remove_rw_check(db: &mut AllowDb, buffer: RwAllowBuffer, slice: &[Cell<u8>])
     assert_eq!(len, slice.len());
 }
 
+// Utility to return a UrAllowBuffer and verify the returned register values
+// match the provided slice.
+fn remove_ur_check(db: &mut AllowDb, buffer: UrAllowBuffer, slice: &[Cell<u8>]) {
+    let (address, len) = db.remove_ur_buffer(buffer);
+    let address: *mut u8 = address.into();
+    assert_eq!(address, slice.as_ptr() as *mut u8);
+    let len: usize = len.into();
+    assert_eq!(len, slice.len());
+}
+
 #[test]
 fn allow_db() {
     let mut db: AllowDb = Default::default();
@@ -76,6 +99,8 @@ fn allow_db() {
         // middle.
         let mut rw_buffer_8_12 = insert_rw_slice(&mut db, &fake_memory[8..=12]).unwrap();
         let ro_buffer_6_7 = insert_ro_slice(&mut db, &fake_memory[6..=7]).unwrap();
+        let ur_buffer_14_15 = insert_ur_slice(&mut db, &fake_memory[14..=15]).unwrap();
+        insert_ur_slice(&mut db, &fake_memory[12..=13]).unwrap_err();
 
         // Check the Deref implementations on the read-only buffers. For the
         // nonempty read-write buffers, we mutate the buffers as well.
@@ -85,6 +110,10 @@ fn allow_db() {
         assert_eq!(*rw_buffer_8_12, [8, 9, 10, 11, 12]);
         rw_buffer_8_12.copy_from_slice(&[20, 21, 22, 23, 24]);
         assert_eq!(*ro_buffer_6_7, [6, 7]);
+        // A userspace-readable buffer can be written by the kernel while the
+        // process reads it.
+        ur_buffer_14_15[1].set(25);
+        assert_eq!(fake_memory[15].get(), 25);
 
         // Remove a buffer, and check it can be re-added.
         remove_ro_check(&mut db, ro_buffer_6_7, &fake_memory[6..=7]);
@@ -96,9 +125,10 @@ fn allow_db() {
         remove_rw_check(&mut db, rw_buffer_3_empty, &fake_memory[3..3]);
         remove_rw_check(&mut db, rw_buffer_8_12, &fake_memory[8..=12]);
         remove_rw_check(&mut db, rw_buffer_6_7, &fake_memory[6..=7]);
+        remove_ur_check(&mut db, ur_buffer_14_15, &fake_memory[14..=15]);
     }
 
     // Verify the values were correctly written into fake_memory.
-    let expected: &mut [u8] = &mut [0, 1, 2, 3, 4, 5, 6, 7, 20, 21, 22, 23, 24, 13, 14, 15];
+    let expected: &mut [u8] = &mut [0, 1, 2, 3, 4, 5, 6, 7, 20, 21, 22, 23, 24, 13, 14, 25];
     assert_eq!(fake_memory, Cell::from_mut(expected).as_slice_of_cells());
 }
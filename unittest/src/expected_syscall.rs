@@ -19,6 +19,18 @@ pub enum ExpectedSyscall {
         skip_upcall: bool,
     },
 
+    YieldWaitFor {
+        // Matched values: yield-wait-for must wait for the specified upcall.
+        driver_num: u32,
+        subscribe_num: u32,
+
+        /// If not `None`, `yield-wait-for` will return the specified upcall
+        /// arguments without consuming a scheduled upcall. If `None`,
+        /// `yield-wait-for` will consume the next scheduled upcall with a
+        /// matching ID and return its arguments.
+        override_return: Option<(u32, u32, u32)>,
+    },
+
     // -------------------------------------------------------------------------
     // Subscribe
     // -------------------------------------------------------------------------
@@ -70,7 +82,33 @@ pub enum ExpectedSyscall {
         // invoked and the provided error will be returned instead.
         return_error: Option<libtock_platform::ErrorCode>,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Userspace-Readable Allow
+    // -------------------------------------------------------------------------
+    AllowUr {
+        driver_num: u32,
+        buffer_num: u32,
+
+        // If set to Some(_), the driver's allow_userspace_readable method will
+        // not be invoked and the provided error will be returned instead.
+        return_error: Option<libtock_platform::ErrorCode>,
+    },
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        // Matched values: the memop call must have the specified memop_num and
+        // argument0 values. Memop operations that do not take an argument are
+        // matched as if argument0 is 0.
+        memop_num: u32,
+        argument0: usize,
+
+        // If set to Some(_), the memop operation will not be performed and the
+        // provided error will be returned instead.
+        return_error: Option<libtock_platform::ErrorCode>,
+    },
     // TODO: Add Exit.
 }
 
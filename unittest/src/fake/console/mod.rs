@@ -0,0 +1,177 @@
+//! Fake implementation of the Console API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00001_console.md
+//!
+//! Like the real API, `Console` prints the bytes it is commanded to write. It
+//! also keeps a copy of the written bytes, which can be retrieved via
+//! `take_bytes` for use in unit tests. Input is provided to the console by
+//! `push_input`. A pending read completes as soon as input is available, with
+//! as many bytes as are available (up to the read's length).
+
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+use std::io::Write;
+
+use crate::upcall;
+
+pub struct Console {
+    written: Cell<Vec<u8>>,
+    max_write_len: Cell<usize>,
+    write_buffer: Cell<RoAllowBuffer>,
+
+    input: RefCell<VecDeque<u8>>,
+    read_buffer: Cell<RwAllowBuffer>,
+    // The length of the pending read, if a read is pending.
+    read_len: Cell<Option<usize>>,
+}
+
+impl Console {
+    pub fn new() -> std::rc::Rc<Console> {
+        std::rc::Rc::new(Console {
+            written: Default::default(),
+            max_write_len: Cell::new(usize::MAX),
+            write_buffer: Default::default(),
+            input: Default::default(),
+            read_buffer: Default::default(),
+            read_len: Cell::new(None),
+        })
+    }
+
+    /// Returns the bytes that have been written, and clears the write log.
+    pub fn take_bytes(&self) -> Vec<u8> {
+        self.written.take()
+    }
+
+    /// Limits the number of bytes each write command writes, to emulate a
+    /// kernel with a small console buffer.
+    pub fn set_max_write_len(&self, max_write_len: usize) {
+        self.max_write_len.set(max_write_len);
+    }
+
+    /// Adds `bytes` to the console's input. If a read is pending, it is
+    /// completed.
+    pub fn push_input(&self, bytes: &[u8]) {
+        self.input.borrow_mut().extend(bytes);
+        self.complete_read();
+    }
+}
+
+impl crate::fake::SyscallDriver for Console {
+    fn id(&self) -> u32 {
+        DRIVER_NUM
+    }
+    fn num_upcalls(&self) -> u32 {
+        3
+    }
+
+    fn command(&self, command_num: u32, argument0: u32, _argument1: u32) -> CommandReturn {
+        match command_num {
+            EXISTS => {}
+            WRITE => {
+                let buffer = self.write_buffer.take();
+                let len = (argument0 as usize)
+                    .min(buffer.len())
+                    .min(self.max_write_len.get());
+                let bytes = &buffer[..len];
+                print!("{}", String::from_utf8_lossy(bytes));
+                let _ = std::io::stdout().flush();
+                let mut written = self.written.take();
+                written.extend_from_slice(bytes);
+                self.written.set(written);
+                self.write_buffer.set(buffer);
+                upcall::schedule(DRIVER_NUM, subscribe::WRITE, (len as u32, 0, 0))
+                    .expect("Unable to schedule upcall");
+            }
+            READ => {
+                if self.read_len.get().is_some() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.read_len.set(Some(argument0 as usize));
+                self.complete_read();
+            }
+            ABORT => {
+                if self.read_len.take().is_some() {
+                    upcall::schedule(
+                        DRIVER_NUM,
+                        subscribe::READ,
+                        (ErrorCode::Cancel as u32, 0, 0),
+                    )
+                    .expect("Unable to schedule upcall");
+                }
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num != allow_ro::WRITE {
+            return Err((buffer, ErrorCode::Invalid));
+        }
+        Ok(self.write_buffer.replace(buffer))
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num != allow_rw::READ {
+            return Err((buffer, ErrorCode::Invalid));
+        }
+        Ok(self.read_buffer.replace(buffer))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests;
+
+const DRIVER_NUM: u32 = 1;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const WRITE: u32 = 1;
+const READ: u32 = 2;
+const ABORT: u32 = 3;
+
+mod subscribe {
+    pub const WRITE: u32 = 1;
+    pub const READ: u32 = 2;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 1;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 1;
+}
+
+impl Console {
+    // Completes the pending read, if a read is pending and input is available.
+    fn complete_read(&self) {
+        let mut input = self.input.borrow_mut();
+        let read_len = match self.read_len.get() {
+            Some(read_len) if !input.is_empty() => read_len,
+            _ => return,
+        };
+        self.read_len.set(None);
+        let mut buffer = self.read_buffer.take();
+        let len = read_len.min(buffer.len()).min(input.len());
+        for (byte, input_byte) in buffer.iter_mut().zip(input.drain(..len)) {
+            *byte = input_byte;
+        }
+        self.read_buffer.set(buffer);
+        upcall::schedule(DRIVER_NUM, subscribe::READ, (0, len as u32, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
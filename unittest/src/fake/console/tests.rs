@@ -0,0 +1,116 @@
+use crate::fake;
+use core::cell::Cell;
+use fake::console::*;
+use libtock_platform::{
+    share, AllowRo, AllowRw, DefaultConfig, ErrorCode, Subscribe, Syscalls, YieldNoWaitReturn,
+};
+
+// Tests the command implementation for commands that do not use buffers.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let console = Console::new();
+    assert!(console.command(EXISTS, 1, 2).is_success());
+    assert!(console.command(ABORT, 0, 0).is_success());
+    assert_eq!(
+        console.command(4, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+}
+
+// Integration test that verifies writes work with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let console = Console::new();
+    kernel.add_driver(&console);
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<fake::Syscalls, DRIVER_NUM, { allow_ro::WRITE }>,
+            Subscribe<fake::Syscalls, DRIVER_NUM, { subscribe::WRITE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(
+            allow_ro, b"Hello",
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::WRITE }>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        // Lengths larger than the buffer are truncated to the buffer.
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 10, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((5,)));
+        assert_eq!(console.take_bytes(), b"Hello");
+
+        console.set_max_write_len(2);
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 5, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((2,)));
+        assert_eq!(console.take_bytes(), b"He");
+    });
+}
+
+// Integration test that verifies reads work with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let console = Console::new();
+    kernel.add_driver(&console);
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut buffer = [0; 4];
+    share::scope::<
+        (
+            AllowRw<fake::Syscalls, DRIVER_NUM, { allow_rw::READ }>,
+            Subscribe<fake::Syscalls, DRIVER_NUM, { subscribe::READ }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(
+            allow_rw,
+            &mut buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READ }>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        // Input that arrives before the read is buffered.
+        console.push_input(b"abcdef");
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 3, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((0, 3)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 4, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((0, 3)));
+
+        // A read without input is pending until input arrives.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 4, 0).is_success());
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, READ, 4, 0).get_failure(),
+            Some(ErrorCode::Busy)
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        console.push_input(b"g");
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((0, 1)));
+
+        // Aborting a pending read completes it with CANCEL.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 4, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, ABORT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.take(), Some((ErrorCode::Cancel as u32, 0)));
+    });
+    assert_eq!(buffer, *b"gef\0");
+}
@@ -1,4 +1,5 @@
 use crate::kernel_data::{with_kernel_data, DriverData, KernelData, KERNEL_DATA};
+use crate::upcall::UpcallId;
 use crate::{ExpectedSyscall, SyscallLogEntry};
 
 /// A fake implementation of the Tock kernel. Used with `fake::Syscalls`, which
@@ -34,8 +35,11 @@ impl Kernel {
                 create_location: std::panic::Location::caller(),
                 drivers: Default::default(),
                 expected_syscalls: Default::default(),
+                memory_layout: Default::default(),
+                process_break: core::ptr::null_mut(),
                 syscall_log: Vec::new(),
                 upcall_queue: Default::default(),
+                null_upcall_queue: Default::default(),
             }))
         });
         if let Some(old_kernel_data) = old_option {
@@ -93,6 +97,46 @@ impl Kernel {
         });
     }
 
+    /// Sets the memory layout reported through the Memop system call, and
+    /// resets the process break to `layout.ram_start`. By default, every
+    /// address in the layout is null.
+    pub fn set_memory_layout(&self, layout: MemoryLayout) {
+        with_kernel_data(|kernel_data| {
+            let kernel_data = kernel_data.unwrap();
+            kernel_data.memory_layout = layout;
+            kernel_data.process_break = layout.ram_start;
+        });
+    }
+
+    /// Returns the current process break, as set by the brk and sbrk Memop
+    /// operations.
+    pub fn process_break(&self) -> *mut u8 {
+        with_kernel_data(|kernel_data| kernel_data.unwrap().process_break)
+    }
+
+    /// Returns true if an upcall is queued, i.e. if a yield-wait call would
+    /// invoke an upcall rather than waiting.
+    pub fn is_upcall_queued(&self) -> bool {
+        with_kernel_data(|kernel_data| !kernel_data.unwrap().upcall_queue.is_empty())
+    }
+
+    /// Returns true if an upcall with the given ID has been scheduled, i.e. if
+    /// a yield-wait-for call for that upcall would return rather than waiting.
+    pub fn is_upcall_scheduled(&self, driver_num: u32, subscribe_num: u32) -> bool {
+        let id = UpcallId {
+            driver_num,
+            subscribe_num,
+        };
+        with_kernel_data(|kernel_data| {
+            let kernel_data = kernel_data.unwrap();
+            kernel_data
+                .upcall_queue
+                .iter()
+                .chain(kernel_data.null_upcall_queue.iter())
+                .any(|entry| entry.id == id)
+        })
+    }
+
     /// Returns the system call log and empties it.
     pub fn take_syscall_log(&self) -> Vec<SyscallLogEntry> {
         with_kernel_data(|kernel_data| std::mem::take(&mut kernel_data.unwrap().syscall_log))
@@ -104,3 +148,33 @@ impl Drop for Kernel {
         KERNEL_DATA.with(|kernel_data| kernel_data.replace(None));
     }
 }
+
+/// The process memory layout that `fake::Kernel` reports through the Memop
+/// system call. The process break may be moved anywhere between `ram_start`
+/// and `grant_region_start` (inclusive).
+///
+/// The fake kernel does not allocate memory for the process. Tests that need
+/// to access memory below the process break (e.g. to test an allocator) should
+/// point `ram_start` at a buffer they own. The fake kernel derives the process
+/// break from `ram_start`, so pointers returned by sbrk may be used to access
+/// that buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryLayout {
+    pub ram_start: *mut u8,
+    pub ram_end: *const u8,
+    pub grant_region_start: *const u8,
+    pub flash_start: *const u8,
+    pub flash_end: *const u8,
+}
+
+impl Default for MemoryLayout {
+    fn default() -> MemoryLayout {
+        MemoryLayout {
+            ram_start: core::ptr::null_mut(),
+            ram_end: core::ptr::null(),
+            grant_region_start: core::ptr::null(),
+            flash_start: core::ptr::null(),
+            flash_end: core::ptr::null(),
+        }
+    }
+}
@@ -45,3 +45,51 @@ fn syscall_log() {
     assert_eq!(kernel.take_syscall_log(), [YieldNoWait, YieldWait]);
     assert_eq!(kernel.take_syscall_log(), []);
 }
+
+#[test]
+fn upcall_queries() {
+    struct MockDriver;
+    impl fake::SyscallDriver for MockDriver {
+        fn id(&self) -> u32 {
+            1
+        }
+        fn num_upcalls(&self) -> u32 {
+            2
+        }
+        fn command(&self, _: u32, _: u32, _: u32) -> libtock_platform::CommandReturn {
+            crate::command_return::failure(libtock_platform::ErrorCode::NoSupport)
+        }
+    }
+    unsafe extern "C" fn upcall(_: u32, _: u32, _: u32, _: libtock_platform::Register) {}
+
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+    assert!(!kernel.is_upcall_queued());
+    assert!(!kernel.is_upcall_scheduled(1, 0));
+
+    // An upcall scheduled with no subscribed upcall is not queued, but can be
+    // received by yield-wait-for.
+    crate::upcall::schedule(1, 0, (0, 0, 0)).unwrap();
+    assert!(!kernel.is_upcall_queued());
+    assert!(kernel.is_upcall_scheduled(1, 0));
+    assert!(!kernel.is_upcall_scheduled(1, 1));
+
+    with_kernel_data(|kernel_data| {
+        kernel_data
+            .unwrap()
+            .drivers
+            .get_mut(&1)
+            .unwrap()
+            .upcalls
+            .insert(
+                1,
+                crate::upcall::Upcall {
+                    fn_pointer: Some(upcall),
+                    data: 0usize.into(),
+                },
+            );
+    });
+    crate::upcall::schedule(1, 1, (0, 0, 0)).unwrap();
+    assert!(kernel.is_upcall_queued());
+    assert!(kernel.is_upcall_scheduled(1, 1));
+}
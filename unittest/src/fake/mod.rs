@@ -10,6 +10,7 @@
 //! (e.g. `fake::Console`).
 
 mod buttons;
+mod console;
 mod kernel;
 mod leds;
 mod low_level_debug;
@@ -17,7 +18,8 @@ mod syscall_driver;
 mod syscalls;
 
 pub use buttons::Buttons;
-pub use kernel::Kernel;
+pub use console::Console;
+pub use kernel::{Kernel, MemoryLayout};
 pub use leds::Leds;
 pub use low_level_debug::{LowLevelDebug, Message};
 pub use syscall_driver::SyscallDriver;
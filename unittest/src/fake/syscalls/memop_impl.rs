@@ -0,0 +1,92 @@
+//! `fake::Kernel`'s implementation of the Memop system call.
+
+use crate::kernel_data::{with_kernel_data, KernelData};
+use crate::{ExpectedSyscall, SyscallLogEntry};
+use libtock_platform::{memop_id, return_variant, ErrorCode, Register};
+use std::convert::TryInto;
+
+pub(super) fn memop(memop_num: Register, argument0: Register) -> [Register; 2] {
+    let memop_num = memop_num.try_into().expect("Too large memop number");
+    let result = with_kernel_data(|option_kernel_data| {
+        let kernel_data = option_kernel_data.expect("Memop called but no fake::Kernel exists");
+
+        kernel_data.syscall_log.push(SyscallLogEntry::Memop {
+            memop_num,
+            argument0: argument0.into(),
+        });
+
+        // Check for an expected syscall entry. Returns an error from the lambda
+        // if this syscall was expected and return_error was specified. Panics
+        // if a different syscall was expected.
+        match kernel_data.expected_syscalls.pop_front() {
+            None => {}
+            Some(ExpectedSyscall::Memop {
+                memop_num: expected_memop_num,
+                argument0: expected_argument0,
+                return_error,
+            }) => {
+                assert_eq!(
+                    memop_num, expected_memop_num,
+                    "expected different memop_num"
+                );
+                assert_eq!(
+                    usize::from(argument0),
+                    expected_argument0,
+                    "expected different argument0"
+                );
+                if let Some(error_code) = return_error {
+                    return Err(error_code);
+                }
+            }
+            Some(expected_syscall) => expected_syscall.panic_wrong_call("Memop"),
+        };
+
+        let layout = kernel_data.memory_layout;
+        match memop_num {
+            memop_id::BRK => {
+                set_break(kernel_data, argument0.into())?;
+                Ok(None)
+            }
+            memop_id::SBRK => {
+                let increment: u32 = argument0.try_into().expect("Too large sbrk increment");
+                let old_break = kernel_data.process_break;
+                let new_break = (old_break as usize).wrapping_add(increment as i32 as usize);
+                set_break(kernel_data, new_break)?;
+                Ok(Some(old_break.into()))
+            }
+            memop_id::PROCESS_RAM_START => Ok(Some(layout.ram_start.into())),
+            memop_id::PROCESS_RAM_END => Ok(Some(layout.ram_end.into())),
+            memop_id::FLASH_START => Ok(Some(layout.flash_start.into())),
+            memop_id::FLASH_END => Ok(Some(layout.flash_end.into())),
+            memop_id::GRANT_REGION_START => Ok(Some(layout.grant_region_start.into())),
+            // The debug hints only affect the kernel's debugging output, which
+            // the fake kernel does not have. Tests can find the hints in the
+            // syscall log.
+            memop_id::DEBUG_STACK_START | memop_id::DEBUG_HEAP_START => Ok(None),
+            _ => Err(ErrorCode::NoSupport),
+        }
+    });
+
+    // Memop operations return Success with u32 if they return a value, and
+    // Success otherwise. TRD 104 does not specify r1 for Success; like the real
+    // kernel, we leave argument0 in it.
+    match result {
+        Ok(None) => [return_variant::SUCCESS.into(), argument0],
+        Ok(Some(value)) => [return_variant::SUCCESS_U32.into(), value],
+        Err(error_code) => [return_variant::FAILURE.into(), error_code.into()],
+    }
+}
+
+// Moves the process break to new_break, or returns NoMem if new_break is
+// outside the range the memory layout permits. The new break is derived from
+// ram_start so that it keeps ram_start's provenance.
+fn set_break(kernel_data: &mut KernelData, new_break: usize) -> Result<(), ErrorCode> {
+    let layout = kernel_data.memory_layout;
+    if new_break < layout.ram_start as usize || new_break > layout.grant_region_start as usize {
+        return Err(ErrorCode::NoMem);
+    }
+    kernel_data.process_break = layout
+        .ram_start
+        .wrapping_add(new_break - layout.ram_start as usize);
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use super::memop_impl::*;
+use crate::{fake, ExpectedSyscall, SyscallLogEntry};
+use libtock_platform::{memop_id, return_variant, ErrorCode, Register, ReturnVariant};
+use std::convert::TryInto;
+use std::panic::catch_unwind;
+
+// Converts the return value of memop into a (return variant, r1) pair.
+fn decode([r0, r1]: [Register; 2]) -> (ReturnVariant, usize) {
+    let r0: u32 = r0.try_into().expect("too large r0");
+    (r0.into(), r1.into())
+}
+
+#[test]
+fn break_operations() {
+    let kernel = fake::Kernel::new();
+    let mut ram = [0u8; 16];
+    let ram_start = ram.as_mut_ptr();
+    kernel.set_memory_layout(fake::MemoryLayout {
+        ram_start,
+        ram_end: ram_start.wrapping_add(16),
+        grant_region_start: ram_start.wrapping_add(12),
+        ..Default::default()
+    });
+    assert_eq!(kernel.process_break(), ram_start);
+
+    // brk within the permitted range.
+    let target = ram_start.wrapping_add(4);
+    let (return_variant, _) = decode(memop(memop_id::BRK.into(), target.into()));
+    assert_eq!(return_variant, return_variant::SUCCESS);
+    assert_eq!(kernel.process_break(), target);
+
+    // brk into the grant region.
+    let (return_variant, r1) = decode(memop(
+        memop_id::BRK.into(),
+        ram_start.wrapping_add(13).into(),
+    ));
+    assert_eq!(return_variant, return_variant::FAILURE);
+    assert_eq!(r1, ErrorCode::NoMem as usize);
+    assert_eq!(kernel.process_break(), target);
+
+    // sbrk upwards, then downwards.
+    let (return_variant, r1) = decode(memop(memop_id::SBRK.into(), 8u32.into()));
+    assert_eq!(return_variant, return_variant::SUCCESS_U32);
+    assert_eq!(r1, target as usize);
+    assert_eq!(kernel.process_break(), ram_start.wrapping_add(12));
+    let (return_variant, r1) = decode(memop(memop_id::SBRK.into(), (-12i32 as u32).into()));
+    assert_eq!(return_variant, return_variant::SUCCESS_U32);
+    assert_eq!(r1, ram_start as usize + 12);
+    assert_eq!(kernel.process_break(), ram_start);
+
+    // sbrk below the start of RAM.
+    let (return_variant, r1) = decode(memop(memop_id::SBRK.into(), (-1i32 as u32).into()));
+    assert_eq!(return_variant, return_variant::FAILURE);
+    assert_eq!(r1, ErrorCode::NoMem as usize);
+    assert_eq!(kernel.process_break(), ram_start);
+
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::BRK,
+                argument0: target as usize,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::BRK,
+                argument0: ram_start as usize + 13,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: 8,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: -12i32 as u32 as usize,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: -1i32 as u32 as usize,
+            },
+        ]
+    );
+}
+
+#[test]
+fn expected_wrong_memop() {
+    let kernel = fake::Kernel::new();
+    let expected_syscall = ExpectedSyscall::Memop {
+        memop_num: memop_id::BRK,
+        argument0: 1,
+        return_error: None,
+    };
+
+    kernel.add_expected_syscall(expected_syscall);
+    assert!(catch_unwind(|| memop(memop_id::SBRK.into(), 1u32.into()))
+        .expect_err("failed to catch wrong memop_num")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different memop_num"));
+
+    kernel.add_expected_syscall(expected_syscall);
+    assert!(catch_unwind(|| memop(memop_id::BRK.into(), 2u32.into()))
+        .expect_err("failed to catch wrong argument0")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different argument0"));
+}
+
+#[test]
+fn layout_queries() {
+    let kernel = fake::Kernel::new();
+    let layout = fake::MemoryLayout {
+        ram_start: 0x2000_0000usize as *mut u8,
+        ram_end: 0x2000_4000usize as *const u8,
+        grant_region_start: 0x2000_3000usize as *const u8,
+        flash_start: 0x3_0000usize as *const u8,
+        flash_end: 0x4_0000usize as *const u8,
+    };
+    kernel.set_memory_layout(layout);
+    for (memop_num, expected) in [
+        (memop_id::PROCESS_RAM_START, 0x2000_0000),
+        (memop_id::PROCESS_RAM_END, 0x2000_4000),
+        (memop_id::FLASH_START, 0x3_0000),
+        (memop_id::FLASH_END, 0x4_0000),
+        (memop_id::GRANT_REGION_START, 0x2000_3000),
+    ] {
+        let (return_variant, r1) = decode(memop(memop_num.into(), 0u32.into()));
+        assert_eq!(return_variant, return_variant::SUCCESS_U32);
+        assert_eq!(r1, expected);
+    }
+
+    for memop_num in [memop_id::DEBUG_STACK_START, memop_id::DEBUG_HEAP_START] {
+        let (return_variant, _) = decode(memop(memop_num.into(), 0x2000_0400u32.into()));
+        assert_eq!(return_variant, return_variant::SUCCESS);
+    }
+
+    let (return_variant, r1) = decode(memop(1234u32.into(), 0u32.into()));
+    assert_eq!(return_variant, return_variant::FAILURE);
+    assert_eq!(r1, ErrorCode::NoSupport as usize);
+}
+
+#[test]
+fn no_kernel() {
+    let result = catch_unwind(|| memop(memop_id::PROCESS_RAM_START.into(), 0u32.into()));
+    assert!(result
+        .expect_err("failed to catch missing kernel")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("no fake::Kernel exists"));
+}
+
+#[test]
+fn return_error() {
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop_id::FLASH_START,
+        argument0: 0,
+        return_error: Some(ErrorCode::Fail),
+    });
+    let (return_variant, r1) = decode(memop(memop_id::FLASH_START.into(), 0u32.into()));
+    assert_eq!(return_variant, return_variant::FAILURE);
+    assert_eq!(r1, ErrorCode::Fail as usize);
+}
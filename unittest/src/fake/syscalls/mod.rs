@@ -1,7 +1,9 @@
 mod allow_ro_impl;
 mod allow_rw_impl;
+mod allow_ur_impl;
 mod command_impl;
 mod exit_impl;
+mod memop_impl;
 mod raw_syscalls_impl;
 mod subscribe_impl;
 mod yield_impl;
@@ -16,10 +18,14 @@ mod allow_ro_impl_tests;
 #[cfg(test)]
 mod allow_rw_impl_tests;
 #[cfg(test)]
+mod allow_ur_impl_tests;
+#[cfg(test)]
 mod command_impl_tests;
 #[cfg(all(not(miri), test))]
 mod exit_impl_tests;
 #[cfg(test)]
+mod memop_impl_tests;
+#[cfg(test)]
 mod raw_syscalls_impl_tests;
 #[cfg(test)]
 mod subscribe_impl_tests;
@@ -7,6 +7,7 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
         match r0.try_into().expect("too-large Yield ID passed") {
             yield_id::NO_WAIT => panic!("yield-no-wait called without an argument"),
             yield_id::WAIT => super::yield_impl::yield_wait(),
+            yield_id::WAIT_FOR => panic!("yield-wait-for called without arguments"),
             id => panic!("unknown yield ID {}", id),
         }
     }
@@ -21,13 +22,29 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
                 // we fail the test case regardless.
                 panic!("yield-wait called with an argument");
             }
+            yield_id::WAIT_FOR => panic!("yield-wait-for called with only 1 argument"),
             id => panic!("unknown yield ID {}", id),
         }
     }
 
-    unsafe fn syscall1<const CLASS: usize>([Register(_r0)]: [Register; 1]) -> [Register; 2] {
+    unsafe fn yield3([r0, r1, r2]: [Register; 3]) -> [Register; 3] {
+        crate::fake::syscalls::assert_valid((r0, r1, r2));
+        match r0.try_into().expect("too-large Yield ID passed") {
+            yield_id::WAIT_FOR => {
+                let (arg0, arg1, arg2) = super::yield_impl::yield_wait_for(
+                    r1.try_into().expect("too-large driver number passed"),
+                    r2.try_into().expect("too-large subscribe number passed"),
+                );
+                [arg0.into(), arg1.into(), arg2.into()]
+            }
+            id => panic!("yield ID {} called with 2 arguments", id),
+        }
+    }
+
+    unsafe fn syscall1<const CLASS: usize>([r0]: [Register; 1]) -> [Register; 2] {
+        crate::fake::syscalls::assert_valid(r0);
         match CLASS {
-            syscall_class::MEMOP => unimplemented!("TODO: Add Memop"),
+            syscall_class::MEMOP => super::memop_impl::memop(r0, 0usize.into()),
             _ => panic!("Unknown syscall1 call. Class: {}", CLASS),
         }
     }
@@ -35,7 +52,7 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
     unsafe fn syscall2<const CLASS: usize>([r0, r1]: [Register; 2]) -> [Register; 2] {
         crate::fake::syscalls::assert_valid((r0, r1));
         match CLASS {
-            syscall_class::MEMOP => unimplemented!("TODO: Add Memop"),
+            syscall_class::MEMOP => super::memop_impl::memop(r0, r1),
             syscall_class::EXIT => super::exit_impl::exit(r0, r1),
             _ => panic!("Unknown syscall2 call. Class: {}", CLASS),
         }
@@ -48,6 +65,7 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
             syscall_class::COMMAND => super::command_impl::command(r0, r1, r2, r3),
             syscall_class::ALLOW_RW => unsafe { super::allow_rw_impl::allow_rw(r0, r1, r2, r3) },
             syscall_class::ALLOW_RO => unsafe { super::allow_ro_impl::allow_ro(r0, r1, r2, r3) },
+            syscall_class::ALLOW_UR => unsafe { super::allow_ur_impl::allow_ur(r0, r1, r2, r3) },
             _ => panic!("Unknown syscall4 call. Class: {}", CLASS),
         }
     }
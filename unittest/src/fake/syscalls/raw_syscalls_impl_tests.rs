@@ -4,7 +4,7 @@
 // tests.
 
 use crate::{fake, SyscallLogEntry};
-use libtock_platform::{syscall_class, RawSyscalls};
+use libtock_platform::{memop_id, syscall_class, RawSyscalls};
 
 #[test]
 fn allow_ro() {
@@ -48,11 +48,52 @@ fn allow_rw() {
     );
 }
 
+#[test]
+fn allow_ur() {
+    let kernel = fake::Kernel::new();
+    unsafe {
+        fake::Syscalls::syscall4::<{ syscall_class::ALLOW_UR }>([
+            1u32.into(),
+            2u32.into(),
+            0u32.into(),
+            0u32.into(),
+        ]);
+    }
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::AllowUr {
+            driver_num: 1,
+            buffer_num: 2,
+            len: 0,
+        }]
+    );
+}
+
 // TODO: Move the syscall4 Command test here.
 
 // TODO: Implement Exit.
 
-// TODO: Implement Memop.
+#[test]
+fn memop() {
+    let kernel = fake::Kernel::new();
+    unsafe {
+        fake::Syscalls::syscall1::<{ syscall_class::MEMOP }>([memop_id::FLASH_START.into()]);
+        fake::Syscalls::syscall2::<{ syscall_class::MEMOP }>([memop_id::SBRK.into(), 0u32.into()]);
+    }
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::FLASH_START,
+                argument0: 0,
+            },
+            SyscallLogEntry::Memop {
+                memop_num: memop_id::SBRK,
+                argument0: 0,
+            },
+        ]
+    );
+}
 
 // TODO: Implement Subscribe.
 
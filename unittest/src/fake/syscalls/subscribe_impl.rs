@@ -108,12 +108,17 @@ pub(super) unsafe fn subscribe(
     };
 
     // Go back into the kernel data to update the stored upcall and purge the
-    // previous upcall from the upcall queue (as required by TRD 104).
+    // previous upcall from the upcall queue (as required by TRD 104). Upcalls
+    // retained for yield-wait-for are purged as well, as the real kernel would
+    // have discarded them.
     let out_upcall = with_kernel_data(|option_kernel_data| {
         let kernel_data = option_kernel_data.unwrap();
         kernel_data
             .upcall_queue
             .retain(|existing_queue_entry| existing_queue_entry.id != upcall_id);
+        kernel_data
+            .null_upcall_queue
+            .retain(|existing_queue_entry| existing_queue_entry.id != upcall_id);
         kernel_data
             .drivers
             .get_mut(&driver_num)
@@ -1,6 +1,7 @@
 //! Implementations of Yield system calls.
 
 use crate::kernel_data::{with_kernel_data, KERNEL_DATA};
+use crate::upcall::UpcallId;
 use crate::{ExpectedSyscall, SyscallLogEntry};
 
 /// # Safety
@@ -64,6 +65,63 @@ pub(super) fn yield_wait() {
     );
 }
 
+pub(super) fn yield_wait_for(driver_num: u32, subscribe_num: u32) -> (u32, u32, u32) {
+    let result = KERNEL_DATA.with(|refcell| {
+        let mut refmut = refcell.borrow_mut();
+        let kernel_data = refmut
+            .as_mut()
+            .expect("yield-wait-for called but no fake::Kernel exists");
+
+        kernel_data.syscall_log.push(SyscallLogEntry::YieldWaitFor {
+            driver_num,
+            subscribe_num,
+        });
+
+        match kernel_data.expected_syscalls.pop_front() {
+            None => {}
+            Some(ExpectedSyscall::YieldWaitFor {
+                driver_num: expected_driver_num,
+                subscribe_num: expected_subscribe_num,
+                override_return,
+            }) => {
+                assert_eq!(
+                    driver_num, expected_driver_num,
+                    "expected different driver_num"
+                );
+                assert_eq!(
+                    subscribe_num, expected_subscribe_num,
+                    "expected different subscribe_num"
+                );
+                if let Some(override_return) = override_return {
+                    return Some(override_return);
+                }
+            }
+            Some(expected_syscall) => expected_syscall.panic_wrong_call("yield-wait-for"),
+        }
+
+        // yield-wait-for returns the upcall's arguments rather than invoking
+        // the upcall, so it accepts upcalls that were scheduled while no upcall
+        // (or a null upcall) was subscribed as well.
+        let id = UpcallId {
+            driver_num,
+            subscribe_num,
+        };
+        for queue in [
+            &mut kernel_data.upcall_queue,
+            &mut kernel_data.null_upcall_queue,
+        ] {
+            if let Some(index) = queue.iter().position(|entry| entry.id == id) {
+                return queue.remove(index).map(|entry| entry.args);
+            }
+        }
+        None
+    });
+
+    // As with yield-wait, a real process would sleep until the upcall is
+    // scheduled, which would hang the test, so we panic instead.
+    result.expect("yield-wait-for called with no queued upcall")
+}
+
 // Pops the next upcall off the kernel data's upcall queue and invokes it, or
 // does nothing if the upcall queue was entry. The return value indicates
 // whether an upcall was run. Panics if no kernel data is present.
@@ -190,6 +190,125 @@ fn yield_wait_test() {
     assert_eq!(kernel.take_syscall_log(), [SyscallLogEntry::YieldWait]);
 }
 
+#[test]
+fn yield_wait_for_test() {
+    // Test calling yield_wait_for with no fake::Kernel present.
+    assert!(catch_unwind(|| yield_wait_for(1, 2))
+        .expect_err("failed to catch missing fake::Kernel")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("no fake::Kernel"));
+
+    let kernel = fake::Kernel::new();
+
+    // Test yield_wait_for with a mismatched expected syscall.
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWait { skip_upcall: false });
+    assert!(catch_unwind(|| yield_wait_for(1, 2))
+        .expect_err("failed to catch mismatched expected syscall")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("yield-wait-for was called instead"));
+    let expected_log_entry = || SyscallLogEntry::YieldWaitFor {
+        driver_num: 1,
+        subscribe_num: 2,
+    };
+    assert_eq!(kernel.take_syscall_log(), [expected_log_entry()]);
+
+    // Test yield_wait_for with mismatched IDs in an expected syscall.
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWaitFor {
+        driver_num: 3,
+        subscribe_num: 2,
+        override_return: None,
+    });
+    assert!(catch_unwind(|| yield_wait_for(1, 2))
+        .expect_err("failed to catch wrong driver_num")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different driver_num"));
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWaitFor {
+        driver_num: 1,
+        subscribe_num: 3,
+        override_return: None,
+    });
+    assert!(catch_unwind(|| yield_wait_for(1, 2))
+        .expect_err("failed to catch wrong subscribe_num")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("expected different subscribe_num"));
+    kernel.take_syscall_log();
+
+    // Test yield_wait_for with a return override in an expected syscall.
+    kernel.add_expected_syscall(ExpectedSyscall::YieldWaitFor {
+        driver_num: 1,
+        subscribe_num: 2,
+        override_return: Some((4, 5, 6)),
+    });
+    assert_eq!(yield_wait_for(1, 2), (4, 5, 6));
+    assert_eq!(kernel.take_syscall_log(), [expected_log_entry()]);
+
+    // Test yield_wait_for with no matching upcall queued.
+    assert!(catch_unwind(|| yield_wait_for(1, 2))
+        .expect_err("failed to catch missing upcall")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("no queued upcall"));
+    kernel.take_syscall_log();
+
+    // Upcall structures for using copy_args.
+    let mut output_array = [0u32; 3];
+    let upcall = Upcall {
+        fn_pointer: Some(copy_args),
+        data: (&mut output_array as *mut u32).into(),
+    };
+    let null_upcall = Upcall {
+        fn_pointer: None,
+        data: 0usize.into(),
+    };
+
+    // Queue an upcall for a different ID, an upcall for the requested ID, and
+    // an upcall for the requested ID that was scheduled while no upcall was
+    // subscribed. yield_wait_for should return the subscribed upcall's
+    // arguments first, without invoking it.
+    with_kernel_data(|option_kernel_data| {
+        let kernel_data = option_kernel_data.unwrap();
+        kernel_data.upcall_queue.push_back(UpcallQueueEntry {
+            args: (1, 1, 1),
+            id: UpcallId {
+                driver_num: 1,
+                subscribe_num: 3,
+            },
+            upcall,
+        });
+        kernel_data.upcall_queue.push_back(UpcallQueueEntry {
+            args: (2, 2, 2),
+            id: UpcallId {
+                driver_num: 1,
+                subscribe_num: 2,
+            },
+            upcall,
+        });
+        kernel_data.null_upcall_queue.push_back(UpcallQueueEntry {
+            args: (3, 3, 3),
+            id: UpcallId {
+                driver_num: 1,
+                subscribe_num: 2,
+            },
+            upcall: null_upcall,
+        });
+    });
+    assert_eq!(yield_wait_for(1, 2), (2, 2, 2));
+    assert_eq!(yield_wait_for(1, 2), (3, 3, 3));
+    assert_eq!(output_array, [0; 3]);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [expected_log_entry(), expected_log_entry()]
+    );
+
+    // The upcall for the other ID should remain queued.
+    yield_wait();
+    assert_eq!(output_array, [1, 1, 1]);
+}
+
 // TODO: Move the yield1 and yield2 tests into a raw_syscalls_impl test module,
 // once all system calls have been implemented.
 
@@ -223,8 +342,16 @@ fn yield1() {
     }
     assert_eq!(kernel.take_syscall_log(), [SyscallLogEntry::YieldWait]);
 
-    // Call yield1 with a yield ID that is unknown but which fits in a u32.
+    // Call yield-wait-for through yield1, which is not valid.
     let result = catch_unwind(|| unsafe { fake::Syscalls::yield1([2u32.into()]) });
+    assert!(result
+        .expect_err("failed to catch yield-wait-for without args")
+        .downcast_ref::<&'static str>()
+        .expect("wrong panic payload type")
+        .contains("yield-wait-for called without arguments"));
+
+    // Call yield1 with a yield ID that is unknown but which fits in a u32.
+    let result = catch_unwind(|| unsafe { fake::Syscalls::yield1([3u32.into()]) });
     assert!(result
         .expect_err("failed to catch incorrect yield ID -- new ID added?")
         .downcast_ref::<String>()
@@ -270,8 +397,16 @@ fn yield2() {
         .expect("wrong panic payload type")
         .contains("yield-wait called with an argument"));
 
-    // Call yield2 with a yield ID that is unknown but which fits in a u32.
+    // Call yield-wait-for through yield2, which should be rejected.
     let result = catch_unwind(|| unsafe { fake::Syscalls::yield2([2u32.into(), 0u32.into()]) });
+    assert!(result
+        .expect_err("failed to catch yield-wait-for with 1 arg")
+        .downcast_ref::<&'static str>()
+        .expect("wrong panic payload type")
+        .contains("yield-wait-for called with only 1 argument"));
+
+    // Call yield2 with a yield ID that is unknown but which fits in a u32.
+    let result = catch_unwind(|| unsafe { fake::Syscalls::yield2([3u32.into(), 0u32.into()]) });
     assert!(result
         .expect_err("failed to catch incorrect yield ID -- new ID added?")
         .downcast_ref::<String>()
@@ -21,8 +21,15 @@ pub(crate) struct KernelData {
 
     pub drivers: std::collections::HashMap<u32, DriverData>,
     pub expected_syscalls: std::collections::VecDeque<crate::ExpectedSyscall>,
+    pub memory_layout: crate::fake::MemoryLayout,
+    // The current process break, as set by the brk and sbrk memop operations.
+    pub process_break: *mut u8,
     pub syscall_log: Vec<crate::SyscallLogEntry>,
     pub upcall_queue: crate::upcall::UpcallQueue,
+    // Upcalls that were scheduled while no upcall (or a null upcall) was
+    // subscribed. They will never be invoked, but yield-wait-for can receive
+    // them.
+    pub null_upcall_queue: crate::upcall::UpcallQueue,
 }
 
 // KERNEL_DATA is set to Some in `fake::Kernel::new` and set to None when the
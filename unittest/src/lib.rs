@@ -13,7 +13,7 @@ mod kernel_data;
 mod syscall_log;
 pub mod upcall;
 
-pub use allow_db::{RoAllowBuffer, RwAllowBuffer};
+pub use allow_db::{RoAllowBuffer, RwAllowBuffer, UrAllowBuffer};
 #[cfg(not(miri))]
 pub use exit_test::{exit_test, ExitCall};
 pub use expected_syscall::ExpectedSyscall;
@@ -8,6 +8,11 @@ pub enum SyscallLogEntry {
 
     YieldWait,
 
+    YieldWaitFor {
+        driver_num: u32,
+        subscribe_num: u32,
+    },
+
     // -------------------------------------------------------------------------
     // Subscribe
     // -------------------------------------------------------------------------
@@ -43,6 +48,23 @@ pub enum SyscallLogEntry {
         buffer_num: u32,
         len: usize,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Userspace-Readable Allow
+    // -------------------------------------------------------------------------
+    AllowUr {
+        driver_num: u32,
+        buffer_num: u32,
+        len: usize,
+    },
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        memop_num: u32,
+        // Memop operations that do not take an argument log an argument0 of 0.
+        argument0: usize,
+    },
     // TODO: Add Exit.
 }
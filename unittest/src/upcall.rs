@@ -1,8 +1,10 @@
 /// Adds an upcall to the upcall queue, to be invoked during a future Yield
 /// call. Operate's on this thread's `fake::Kernel`.
 ///
-/// Like the real kernel, this does nothing and returns success if there is no
-/// upcall or the upcall is a null upcall.
+/// Like the real kernel, this does not queue an upcall to be invoked and returns
+/// success if there is no upcall or the upcall is a null upcall. However, the
+/// upcall's arguments are retained so that a later yield-wait-for call can
+/// receive them, as if the process had already been waiting for the upcall.
 pub fn schedule(
     driver_num: u32,
     subscribe_num: u32,
@@ -20,23 +22,30 @@ pub fn schedule(
                 requested: subscribe_num,
             });
         }
+        let id = UpcallId {
+            driver_num,
+            subscribe_num,
+        };
         let upcall = match driver_data.upcalls.get(&subscribe_num) {
-            Some(&upcall) => upcall,
-            None => return Ok(()),
+            Some(&upcall) if !upcall.is_null() => upcall,
+            // Don't queue a null upcall for invocation, as they don't do
+            // anything when invoked anyway, and the core kernel does not queue
+            // them either.
+            _ => {
+                kernel_data.null_upcall_queue.push_back(UpcallQueueEntry {
+                    args,
+                    id,
+                    upcall: Upcall {
+                        fn_pointer: None,
+                        data: 0usize.into(),
+                    },
+                });
+                return Ok(());
+            }
         };
-        // Don't bother queueing a null upcall, as they don't do anything when
-        // invoked anyway, and the core kernel does not queue them either.
-        if upcall.is_null() {
-            return Ok(());
-        }
-        kernel_data.upcall_queue.push_back(UpcallQueueEntry {
-            args,
-            id: UpcallId {
-                driver_num,
-                subscribe_num,
-            },
-            upcall,
-        });
+        kernel_data
+            .upcall_queue
+            .push_back(UpcallQueueEntry { args, id, upcall });
         Ok(())
     })
 }
@@ -159,8 +168,9 @@ mod tests {
             let kernel_data = kernel_data.unwrap();
 
             // There was no upcall to schedule, so the queue should still be
-            // empty.
+            // empty. The arguments should be retained for yield-wait-for.
             assert!(kernel_data.upcall_queue.is_empty());
+            assert_eq!(kernel_data.null_upcall_queue.len(), 1);
 
             // Register a null upcall.
             kernel_data.drivers.get_mut(&1).unwrap().upcalls.insert(
@@ -180,6 +190,7 @@ mod tests {
 
             // Very the upcall was not queued.
             assert!(kernel_data.upcall_queue.is_empty());
+            assert_eq!(kernel_data.null_upcall_queue.len(), 2);
 
             // Register a non-null upcall.
             kernel_data.drivers.get_mut(&1).unwrap().upcalls.insert(